@@ -1,9 +1,90 @@
-use crate::config::{AppConfig, MouseButton, HotkeyConfig, IntervalMode};
-use crate::hotkey_manager::{HotkeyAction, HotkeyManager};
-use crate::mouse_controller::MouseController;
+use crate::tray_manager::{TrayEvent, TrayManager};
+use rust_ac::benchmark::{self, BenchmarkResult};
+use rust_ac::changelog::{self, Release};
+use rust_ac::click_hook::{self, ClickHook};
+use rust_ac::command_file::{self, CommandFile, FileCommand};
+use rust_ac::config::{
+    ActionStep, AppConfig, BoundAction, ClickPoint, MouseButton, HotkeyBinding, HotkeyConfig, HotkeyPreset, IntervalMode, LimitAction,
+    PointOrder, RunMode, ScrollDirection, TimeOfDay,
+};
+use rust_ac::hotkey_manager::{HotkeyAction, HotkeyManager};
+use rust_ac::input_backend::{self, DryRunBackend};
+use rust_ac::logging::{self, LogLevel};
+use rust_ac::monitor;
+use rust_ac::mouse_controller::{ClickSettings, DragSettings, MouseController};
+use rust_ac::overlay::OverlayWindow;
+use rust_ac::target_window;
+use rust_ac::{log_error, log_info, log_warn};
 
 use eframe::egui;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
 use std::time::Instant;
+use winapi::um::winuser::{GetAsyncKeyState, VK_ESCAPE};
+use windows::Win32::Foundation::HWND;
+
+/// 性能测试的运行时长（秒）
+const BENCHMARK_SECS: u64 = 3;
+
+/// 日志窗口显示的最大行数
+const LOG_VIEW_LINES: usize = 200;
+
+/// 倒计时覆盖层的文字颜色（0x00BBGGRR）
+const COUNTDOWN_COLOR: u32 = 0x0000A5FF;
+
+/// 开始连点前的倒计时
+struct StartCountdown {
+    deadline: Instant,
+    /// 覆盖层创建失败时为 None，倒计时仍然有效
+    overlay: Option<OverlayWindow>,
+    shown_secs: u64,
+    mode: RunMode,
+}
+
+/// 空闲时检测热键的重绘间隔
+const IDLE_REPAINT: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// 省电模式下窗口隐藏且空闲时的重绘间隔，每秒唤醒次数从 10 次降到 2 次
+const POWER_SAVING_REPAINT: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// 连点中从托盘退出时，两次点击“退出程序”需在此时间内完成
+const QUIT_CONFIRM_WINDOW: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// 按统计刷新间隔采样的运行数据，避免数字每帧跳动
+#[derive(Default)]
+struct StatsSnapshot {
+    clicks: u64,
+    cps: f64,
+    running_time: Option<std::time::Duration>,
+}
+
+impl StatsSnapshot {
+    fn sample(controller: &MouseController) -> Self {
+        Self {
+            clicks: controller.get_click_count(),
+            cps: controller.get_cps(),
+            running_time: controller.get_running_time(),
+        }
+    }
+}
+
+/// 热键设置中可选的主按键（按类别分组）
+const HOTKEY_KEYS: [(&str, &[&str]); 8] = [
+    ("功能键", &["F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12"]),
+    ("字母键", &["A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S", "T", "U", "V", "W", "X", "Y", "Z"]),
+    ("符号键", &["Minus", "Equal", "BracketLeft", "BracketRight", "Semicolon", "Quote", "Backquote", "Backslash", "Comma", "Period", "Slash"]),
+    ("导航键", &["Home", "End", "PageUp", "PageDown", "Insert", "Delete"]),
+    ("锁定键", &["CapsLock", "NumLock", "ScrollLock"]),
+    ("特殊键", &["Space", "Enter", "Esc", "Tab"]),
+    ("仅修饰键", &HotkeyConfig::DOUBLE_TAP_KEYS),
+    ("手柄", &HotkeyConfig::GAMEPAD_KEYS),
+];
+
+/// 只能轮询检测、不经过系统注册的按键类别，仅切换热键可用
+const POLLED_KEY_CATEGORIES: [&str; 2] = ["仅修饰键", "手柄"];
+
+/// 热键设置中可选的修饰键
+const HOTKEY_MODIFIERS: [&str; 4] = ["Ctrl", "Alt", "Shift", "Win"];
 
 pub struct AutoClickerApp {
     config: AppConfig,
@@ -15,37 +96,125 @@ pub struct AutoClickerApp {
     cps_input: String,
     last_update: Instant,
     last_stats_update: Instant,
+    stats: StatsSnapshot,
     hotkey_error: Option<String>,
     show_hotkey_settings: bool,
     temp_toggle_hotkey: HotkeyConfig,
+    temp_suspend_hotkey: HotkeyConfig,
+    temp_hotkey_bindings: Vec<HotkeyBinding>,
+    /// 生成点击节奏时的“点击 N 次、跳过 M 次”
+    pattern_fill: (usize, usize),
+    /// 外部脚本写入的命令文件，未启用时为 None
+    command_file: Option<CommandFile>,
+    show_reset_confirm: bool,
+    /// 窗口可见且正在连点时，退出前弹出确认
+    show_quit_confirm: bool,
+    /// 窗口隐藏且正在连点时，第一次点击托盘“退出程序”的时间，需在时限内再次点击才退出
+    quit_armed_at: Option<Instant>,
     style_initialized: bool,
+    settings_filter: String,
+    show_macro_editor: bool,
+    monitor_layout: String,
+    capture_deadline: Option<Instant>,
+    capture_relative: bool,
+    /// 开始连点时的前台窗口，失去焦点时停止
+    focus_window: Option<HWND>,
+    /// 记录用户最近一次真实点击位置，只在编辑固定坐标时安装
+    click_hook: Option<ClickHook>,
+    click_hook_failed: bool,
+    tray_manager: Option<TrayManager>,
+    last_tray_status: String,
+    last_tray_update: Instant,
+    profiles: Vec<String>,
+    profile_name_input: String,
+    wizard_step: Option<usize>,
+    /// 向导中选择的点击频率和触发模式，完成时才写入配置，跳过时丢弃
+    wizard_cps_value: u64,
+    wizard_hold_mode: bool,
+    countdown: Option<StartCountdown>,
+    /// 当前（或最近一次）连点的启动方式
+    run_mode: RunMode,
+    /// 远程会话中已提示过一次，之后开始时不再拦截
+    remote_session_confirmed: bool,
+    refresh_rate: Option<u32>,
+    hotkey_presets: Vec<HotkeyPreset>,
+    show_log_viewer: bool,
+    log_lines: Vec<String>,
+    last_log_refresh: Instant,
+    /// 按住预览时的空跑引擎
+    speed_preview: Option<MouseController>,
+    /// 运行中重置计数时本次会话已有的点击数
+    clicks_before_reset: u64,
+    hotkey_preset_name: String,
+    show_benchmark: bool,
+    /// 待显示的更新说明，为空时不显示“新版本”窗口
+    whats_new: Vec<&'static Release>,
+    benchmark_rx: Option<Receiver<BenchmarkResult>>,
+    benchmark_result: Option<BenchmarkResult>,
 }
 
 impl AutoClickerApp {
     pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        let config = AppConfig::load();
+        let mut config = AppConfig::load();
+
+        // 根据当前显示器布局选择对应的固定坐标，找不到时沿用上次的坐标
+        let monitor_layout = monitor::layout_signature();
+        let layout_matched = config.select_monitor_profile(&monitor_layout);
+        if !layout_matched && !config.monitor_profiles.is_empty() {
+            log_info!("未找到显示器布局 {} 的坐标，沿用上次坐标", monitor_layout);
+        }
         let interval_input = config.click_interval.to_string();
         let cps_input = config.cps_value.to_string();
 
         // 初始化热键管理器
         let (hotkey_manager, initial_hotkey_error) = match HotkeyManager::new() {
             Ok(mut manager) => {
-                match manager.update_hotkeys(&config.toggle_hotkey) {
+                manager.set_revalidate_interval(std::time::Duration::from_secs(config.hotkey_revalidate_secs));
+                match Self::register_all_hotkeys(&mut manager, &config) {
                     Ok(_) => (Some(manager), None),
                     Err(e) => {
-                        eprintln!("热键注册失败: {}", e);
+                        log_error!("热键注册失败: {}", e);
                         (Some(manager), Some(e))
                     }
                 }
             }
             Err(e) => {
-                eprintln!("热键管理器初始化失败: {}", e);
+                log_error!("热键管理器初始化失败: {}", e);
                 (None, Some(format!("热键管理器初始化失败: {}", e)))
             }
         };
 
+        // 初始化托盘图标
+        let profiles = AppConfig::list_profiles();
+        let tray_manager = match TrayManager::new() {
+            Ok(mut tray) => {
+                tray.set_profiles(&profiles, config.active_profile.as_deref());
+                Some(tray)
+            }
+            Err(e) => {
+                log_error!("托盘初始化失败: {}", e);
+                None
+            }
+        };
+        let profile_name_input = config.active_profile.clone().unwrap_or_default();
+        let config_first_run = !config.first_run_complete;
+        // 全新安装只走引导，不显示更新说明
+        if config_first_run {
+            config.last_seen_version = Some(changelog::CURRENT_VERSION.to_string());
+        }
+        let whats_new = changelog::unseen_releases(config.last_seen_version.as_deref());
+
         Self {
             temp_toggle_hotkey: config.toggle_hotkey.clone(),
+            temp_suspend_hotkey: config.suspend_hotkey.clone(),
+            temp_hotkey_bindings: config.hotkey_bindings.clone(),
+            pattern_fill: (3, 2),
+            command_file: None,
+            show_reset_confirm: false,
+            show_quit_confirm: false,
+            quit_armed_at: None,
+            wizard_cps_value: config.cps_value,
+            wizard_hold_mode: config.hold_mode,
             config,
             mouse_controller: MouseController::new(),
             hotkey_manager,
@@ -59,9 +228,39 @@ impl AutoClickerApp {
             cps_input,
             last_update: Instant::now(),
             last_stats_update: Instant::now(),
+            stats: StatsSnapshot::default(),
             hotkey_error: initial_hotkey_error,
             show_hotkey_settings: false,
             style_initialized: false,
+            settings_filter: String::new(),
+            show_macro_editor: false,
+            monitor_layout,
+            capture_deadline: None,
+            capture_relative: false,
+            focus_window: None,
+            click_hook: None,
+            click_hook_failed: false,
+            tray_manager,
+            last_tray_status: String::new(),
+            last_tray_update: Instant::now(),
+            wizard_step: if config_first_run { Some(0) } else { None },
+            profiles,
+            profile_name_input,
+            countdown: None,
+            run_mode: RunMode::Real,
+            remote_session_confirmed: false,
+            refresh_rate: monitor::refresh_rate(),
+            hotkey_presets: HotkeyPreset::load_all(),
+            show_log_viewer: false,
+            log_lines: Vec::new(),
+            last_log_refresh: Instant::now(),
+            speed_preview: None,
+            clicks_before_reset: 0,
+            hotkey_preset_name: String::new(),
+            show_benchmark: false,
+            whats_new,
+            benchmark_rx: None,
+            benchmark_result: None,
         }
     }
 
@@ -70,17 +269,19 @@ impl AutoClickerApp {
             if let Some(action) = hotkey_manager.check_events(self.config.hold_mode) {
                 match action {
                     HotkeyAction::Toggle => {
-                        // 切换模式：按一次切换状态
-                        if self.config.is_running {
+                        // 切换模式：按一次切换状态，倒计时中则取消
+                        if self.countdown.is_some() {
+                            self.cancel_countdown();
+                        } else if self.config.is_running {
                             self.stop_clicking();
                         } else {
-                            self.start_clicking();
+                            self.start_clicking(self.config.hotkey_run_mode);
                         }
                     }
                     HotkeyAction::HoldStart => {
                         // 长按模式：按下开始
                         if !self.config.is_running {
-                            self.start_clicking();
+                            self.start_clicking(self.config.hotkey_run_mode);
                         }
                     }
                     HotkeyAction::HoldStop => {
@@ -89,6 +290,25 @@ impl AutoClickerApp {
                             self.stop_clicking();
                         }
                     }
+                    HotkeyAction::SuspendChanged(suspended) => {
+                        if suspended {
+                            // 挂起后无法再收到松开事件，先停止连点
+                            if self.countdown.is_some() {
+                                self.cancel_countdown();
+                            }
+                            if self.config.is_running {
+                                self.stop_clicking();
+                            }
+                            self.status_message = "热键已挂起".to_string();
+                        } else {
+                            self.status_message = "热键已恢复".to_string();
+                        }
+                    }
+                    HotkeyAction::RegistrationLost(e) => {
+                        self.status_message = "热键失效，可能被其他程序占用".to_string();
+                        self.hotkey_error = Some(e);
+                    }
+                    HotkeyAction::Bound(action) => self.run_bound_action(action),
                 }
             }
         }
@@ -96,7 +316,40 @@ impl AutoClickerApp {
 
 
 
-    fn start_clicking(&mut self) {
+    fn start_clicking(&mut self, mode: RunMode) {
+        let quiet_hours = self.config.active_quiet_hours();
+        if let Some(hours) = quiet_hours
+            && hours.contains(TimeOfDay::now())
+        {
+            self.status_message = format!("当前处于禁止时段 {}，不会开始连点", hours);
+            return;
+        }
+        // 远程会话中注入的点击可能被丢弃，第一次开始时只提示，再次开始才真正连点
+        if mode != RunMode::DryRun && !self.remote_session_confirmed && input_backend::is_remote_session() {
+            self.remote_session_confirmed = true;
+            self.status_message = "远程会话中，点击可能无效，再次开始以继续".to_string();
+            return;
+        }
+        // 连点期间不需要记录点击位置，开始前先卸载全局鼠标钩子
+        self.click_hook = None;
+        self.mouse_controller.set_quiet_hours(quiet_hours);
+        self.mouse_controller.set_pause_freezes_stats(self.config.pause_freezes_stats);
+
+        if self.config.macro_mode || self.config.drag_mode {
+            // 宏和拖拽直接驱动输入后端，只支持真实执行
+            if mode != RunMode::Real {
+                self.status_message = format!("宏和拖拽模式不支持{}", mode);
+                return;
+            }
+            self.run_mode = mode;
+            if self.config.macro_mode {
+                self.start_macro();
+            } else {
+                self.start_drag();
+            }
+            return;
+        }
+
         // 根据当前模式更新配置
         match self.config.interval_mode {
             IntervalMode::Milliseconds => {
@@ -119,10 +372,34 @@ impl AutoClickerApp {
             }
         }
 
-        let effective_interval = self.config.get_effective_interval();
-        self.mouse_controller
-            .start_clicking(self.config.mouse_button, effective_interval);
+        let effective_interval = self.config.capped_interval(self.refresh_rate);
+        let mut settings = ClickSettings::new(self.config.mouse_button, effective_interval);
+        if self.config.fixed_position {
+            if self.config.click_points.is_empty() {
+                self.status_message = "请先添加固定坐标".to_string();
+                return;
+            }
+            settings.points = self.config.click_points.clone();
+            settings.point_order = self.config.point_order;
+            if self.config.glide_between_points {
+                settings.glide_speed = self.config.glide_speed;
+            }
+            settings.target_window = self.config.target_window.clone();
+        }
+        settings.reassert_position = self.config.reassert_position;
+        settings.mode = mode;
+        self.run_mode = mode;
+        settings.click_limit = self.config.click_limit;
+        settings.on_limit = self.config.on_limit.clone();
+        settings.start_jitter_ms = (self.config.start_jitter_min_ms, self.config.start_jitter_max_ms);
+        settings.pattern = self.config.click_pattern.clone();
+        settings.position_jitter = self.config.position_jitter_px;
+        settings.hold_ms = (self.config.hold_min_ms, self.config.hold_max_ms);
+        settings.interval_jitter_pct = self.config.interval_jitter_pct;
+
+        self.mouse_controller.start_clicking(settings);
         self.config.is_running = true;
+        self.refresh_stats();
 
         let mode_text = match self.config.interval_mode {
             IntervalMode::Milliseconds => format!("{}ms间隔", effective_interval),
@@ -131,180 +408,1615 @@ impl AutoClickerApp {
 
         self.status_message = format!("正在连点 - {} ({})",
             self.config.mouse_button, mode_text);
+        if mode != RunMode::Real {
+            self.status_message = format!("{} - {}", mode, self.status_message);
+        }
+        if input_backend::is_remote_session() {
+            self.status_message.push_str(" - 远程会话中，点击可能无效");
+        }
     }
 
-    fn stop_clicking(&mut self) {
-        self.mouse_controller.stop_clicking();
-        self.config.is_running = false;
-        self.status_message = "已停止".to_string();
+    fn start_macro(&mut self) {
+        let steps = &self.config.macro_steps;
+        if steps.is_empty() {
+            self.status_message = "宏步骤为空".to_string();
+            return;
+        }
+        // 没有等待步骤时宏会空转占满CPU
+        if !steps.iter().any(|step| matches!(step, ActionStep::Wait { ms } if *ms > 0)) {
+            self.status_message = "宏中至少需要一个大于0的等待步骤".to_string();
+            return;
+        }
+
+        let step_count = steps.len();
+        self.mouse_controller.start_macro(steps.clone());
+        let _ = self.config.save();
+        self.config.is_running = true;
+        self.refresh_stats();
+        self.status_message = format!("正在执行宏 ({} 步)", step_count);
     }
-}
 
-impl eframe::App for AutoClickerApp {
-    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
-        // 提高热键检查频率，确保响应及时
-        let now = Instant::now();
-        if now.duration_since(self.last_update) >= std::time::Duration::from_millis(10) {
-            self.handle_hotkey_events();
-            self.last_update = now;
+    fn begin_countdown(&mut self, secs: u32, mode: RunMode) {
+        let overlay = match OverlayWindow::centered(300, 300, 220) {
+            Ok(overlay) => Some(overlay),
+            Err(e) => {
+                log_warn!("倒计时覆盖层创建失败: {}", e);
+                None
+            }
+        };
+        self.countdown = Some(StartCountdown {
+            deadline: Instant::now() + std::time::Duration::from_secs(secs as u64),
+            overlay,
+            shown_secs: 0,
+            mode,
+        });
+        self.status_message = format!("{}秒后开始连点 (Esc 取消)", secs);
+    }
+
+    fn cancel_countdown(&mut self) {
+        self.countdown = None;
+        self.status_message = "已取消开始".to_string();
+    }
+
+    /// 只在编辑固定坐标且未在连点时安装鼠标钩子，供“上次点击位置”使用
+    fn sync_click_hook(&mut self) {
+        let wanted = self.config.fixed_position && !self.config.is_running && self.countdown.is_none();
+        if !wanted {
+            self.click_hook = None;
+            return;
+        }
+        if self.click_hook.is_some() || self.click_hook_failed {
+            return;
+        }
+        match ClickHook::install() {
+            Ok(hook) => self.click_hook = Some(hook),
+            Err(e) => {
+                // 只提示一次，不再每帧重试
+                log_warn!("{}，无法使用上次点击位置", e);
+                self.click_hook_failed = true;
+            }
         }
+    }
 
-        // 只初始化一次样式，避免每帧都克隆
-        if !self.style_initialized {
-            let mut style = (*ctx.style()).clone();
-            style.spacing.button_padding = egui::vec2(12.0, 8.0);
-            style.spacing.item_spacing = egui::vec2(8.0, 8.0);
-            ctx.set_style(style);
-            self.style_initialized = true;
+    /// 刷新倒计时覆盖层，结束时开始连点
+    fn update_countdown(&mut self, ctx: &egui::Context) {
+        let Some(countdown) = self.countdown.as_mut() else {
+            return;
+        };
+
+        // 主窗口可能已最小化，用全局按键状态检测 Esc
+        let esc_pressed = unsafe { GetAsyncKeyState(VK_ESCAPE) < 0 }
+            || ctx.input(|i| i.key_pressed(egui::Key::Escape));
+        if esc_pressed {
+            self.cancel_countdown();
+            return;
         }
 
-        // 主窗口UI
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.vertical_centered(|ui| {
-                ui.add_space(10.0);
-                ui.heading("鼠标连点器");
-                ui.add_space(5.0);
-                ui.label("高效、简洁的自动点击工具");
-            });
+        let remaining = countdown.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            let mode = countdown.mode;
+            self.countdown = None;
+            self.start_clicking(mode);
+            return;
+        }
 
-            ui.separator();
-            ui.add_space(10.0);
+        let secs = remaining.as_secs_f64().ceil() as u64;
+        if secs != countdown.shown_secs {
+            countdown.shown_secs = secs;
+            if let Some(overlay) = countdown.overlay.as_mut() {
+                overlay.set_text(&secs.to_string(), COUNTDOWN_COLOR);
+            }
+        }
+    }
 
-            // 主要控制区域
-            ui.group(|ui| {
-                ui.set_min_width(350.0);
+    fn refresh_log_lines(&mut self) {
+        self.last_log_refresh = Instant::now();
+        match logging::read_tail(LOG_VIEW_LINES) {
+            Ok(lines) => self.log_lines = lines,
+            Err(e) => self.log_lines = vec![format!("[ERROR] {}", e)],
+        }
+    }
+
+    fn show_log_viewer_window(&mut self, ctx: &egui::Context) {
+        // 窗口打开期间每秒刷新一次
+        if self.last_log_refresh.elapsed() >= std::time::Duration::from_secs(1) {
+            self.refresh_log_lines();
+        }
 
-                // 鼠标按键选择
+        let mut open = true;
+        egui::Window::new("日志")
+            .open(&mut open)
+            .default_width(520.0)
+            .default_height(320.0)
+            .show(ctx, |ui| {
                 ui.horizontal(|ui| {
-                    ui.label("鼠标按键:");
-                    if ui.radio_value(&mut self.config.mouse_button, MouseButton::Left, "左键").changed() {
-                        let _ = self.config.save();
+                    if ui.button("刷新").clicked() {
+                        self.refresh_log_lines();
                     }
-                    if ui.radio_value(&mut self.config.mouse_button, MouseButton::Right, "右键").changed() {
-                        let _ = self.config.save();
+                    if ui.button("打开日志文件").clicked() {
+                        match logging::log_path() {
+                            Ok(path) if path.exists() => {
+                                if let Err(e) = std::process::Command::new("explorer").arg(&path).spawn() {
+                                    self.status_message = format!("打开日志文件失败: {}", e);
+                                }
+                            }
+                            Ok(_) => self.status_message = "日志文件尚未创建".to_string(),
+                            Err(e) => self.status_message = e,
+                        }
                     }
+                    ui.weak(format!("最近 {} 行", LOG_VIEW_LINES));
                 });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .stick_to_bottom(true)
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        if self.log_lines.is_empty() {
+                            ui.weak("暂无日志");
+                        }
+                        for line in &self.log_lines {
+                            let color = match LogLevel::of_line(line) {
+                                LogLevel::Error => egui::Color32::from_rgb(220, 80, 80),
+                                LogLevel::Warn => egui::Color32::from_rgb(220, 160, 40),
+                                LogLevel::Info => ui.visuals().text_color(),
+                            };
+                            ui.label(egui::RichText::new(line).monospace().color(color));
+                        }
+                    });
+            });
 
-                ui.add_space(8.0);
+        if !open {
+            self.show_log_viewer = false;
+        }
+    }
 
-                // 间隔模式选择
-                ui.horizontal(|ui| {
-                    ui.label("间隔模式:");
-                    if ui.radio_value(&mut self.config.interval_mode, IntervalMode::Milliseconds, "毫秒").changed() {
-                        let _ = self.config.save();
-                    }
-                    if ui.radio_value(&mut self.config.interval_mode, IntervalMode::CPS, "CPS").changed() {
-                        let _ = self.config.save();
+    fn show_whats_new_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut dismissed = false;
+
+        egui::Window::new(format!("新版本 {}", changelog::CURRENT_VERSION))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for release in &self.whats_new {
+                        ui.strong(release.version);
+                        for change in release.changes {
+                            ui.label(format!("• {}", change));
+                        }
+                        ui.add_space(5.0);
                     }
                 });
-
                 ui.add_space(5.0);
+                dismissed = ui.button("知道了").clicked();
+            });
 
-                // 间隔时间设置
-                match self.config.interval_mode {
-                    IntervalMode::Milliseconds => {
-                        ui.horizontal(|ui| {
-                            ui.label("点击间隔:");
-                            if ui.add(egui::DragValue::new(&mut self.config.click_interval)
-                                .speed(1.0)
-                                .clamp_range(1..=10000)
-                                .suffix(" ms")).changed() {
-                                let _ = self.config.save();
-                                self.interval_input = self.config.click_interval.to_string();
-                            }
+        if !open || dismissed {
+            self.whats_new.clear();
+            if self.config.last_seen_version.as_deref() != Some(changelog::CURRENT_VERSION) {
+                self.config.last_seen_version = Some(changelog::CURRENT_VERSION.to_string());
+                let _ = self.config.save();
+            }
+        }
+    }
 
-                            if ui.small_button("快速").clicked() {
-                                self.config.click_interval = 50;
-                                self.interval_input = "50".to_string();
-                                let _ = self.config.save();
-                                self.cps_input = self.config.cps_value.to_string();
-                            }
-                            if ui.small_button("中等").clicked() {
-                                self.config.click_interval = 100;
-                                self.interval_input = "100".to_string();
-                                let _ = self.config.save();
-                            }
-                            if ui.small_button("慢速").clicked() {
-                                self.config.click_interval = 500;
-                                self.interval_input = "500".to_string();
-                                let _ = self.config.save();
-                            }
+    /// 在后台线程运行性能测试，避免阻塞界面
+    fn start_benchmark(&mut self) {
+        if self.config.is_running || self.benchmark_rx.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(benchmark::run(std::time::Duration::from_secs(BENCHMARK_SECS)));
+        });
+        self.benchmark_rx = Some(rx);
+        self.benchmark_result = None;
+        self.status_message = "正在进行性能测试...".to_string();
+    }
+
+    fn poll_benchmark(&mut self) {
+        let Some(rx) = self.benchmark_rx.as_ref() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.benchmark_rx = None;
+                self.benchmark_result = Some(result);
+                self.status_message = format!("性能测试完成: {:.0} CPS", result.cps);
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.benchmark_rx = None;
+                self.status_message = "性能测试失败".to_string();
+            }
+        }
+    }
+
+    fn show_benchmark_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        egui::Window::new("性能测试")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                match self.benchmark_result {
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(format!("正在空跑测试（约{}秒，不会产生真实点击）...", BENCHMARK_SECS));
                         });
                     }
-                    IntervalMode::CPS => {
-                        ui.horizontal(|ui| {
-                            ui.label("点击频率:");
-                            if ui.add(egui::DragValue::new(&mut self.config.cps_value)
-                                .speed(1.0)
-                                .clamp_range(1..=1000)
-                                .suffix(" CPS")).changed() {
-                                let _ = self.config.save();
-                            }
-
-                            if ui.small_button("慢速").clicked() {
-                                self.config.cps_value = 2;
-                                self.cps_input = "2".to_string();
-                                let _ = self.config.save();
-                            }
-                            if ui.small_button("中等").clicked() {
-                                self.config.cps_value = 10;
-                                self.cps_input = "10".to_string();
-                                let _ = self.config.save();
-                            }
-                            if ui.small_button("快速").clicked() {
-                                self.config.cps_value = 20;
-                                self.cps_input = "20".to_string();
-                                let _ = self.config.save();
-                            }
+                    Some(result) => {
+                        egui::Grid::new("benchmark_grid").num_columns(2).show(ui, |ui| {
+                            ui.label("最高频率:");
+                            ui.strong(format!("{:.1} CPS", result.cps));
+                            ui.end_row();
+                            ui.label("平均间隔:");
+                            ui.label(format!("{:.3} ms", result.mean_interval_ms));
+                            ui.end_row();
+                            ui.label("间隔抖动:");
+                            ui.label(format!("±{:.3} ms", result.jitter_ms));
+                            ui.end_row();
+                            ui.label("工作线程:");
+                            ui.label(result.threads.to_string());
+                            ui.end_row();
+                            ui.label("点击次数:");
+                            ui.label(result.clicks.to_string());
+                            ui.end_row();
                         });
-                        
-                        // 高 CPS 警告
-                        if self.config.cps_value > 100 {
-                            ui.colored_label(
-                                egui::Color32::from_rgb(255, 165, 0),
-                                format!("⚠ 高频率 ({} CPS) 可能影响系统性能", self.config.cps_value)
-                            );
+                        ui.add_space(5.0);
+                        ui.label(egui::RichText::new("实际目标程序能否处理这么高的频率另当别论，建议设置低于该值").weak());
+                        if ui.button("重新测试").clicked() {
+                            self.start_benchmark();
+                        }
+                    }
+                }
+            });
+
+        // 测试进行中关闭窗口不会中断测试，结果在状态栏显示
+        if !open {
+            self.show_benchmark = false;
+        }
+    }
+
+    fn start_drag(&mut self) {
+        if self.config.drag_path.len() < 2 {
+            self.status_message = "拖拽路径至少需要起点和终点".to_string();
+            return;
+        }
+        if self.config.drag_speed == 0 {
+            self.status_message = "拖拽速度必须大于0".to_string();
+            return;
+        }
+
+        // 两次拖拽之间的间隔沿用点击间隔设置
+        let pause_ms = self.config.get_effective_interval();
+        self.mouse_controller.start_drag(DragSettings {
+            button: self.config.mouse_button,
+            path: self.config.drag_path.clone(),
+            speed: self.config.drag_speed,
+            pause_ms,
+        });
+        let _ = self.config.save();
+        self.config.is_running = true;
+        self.refresh_stats();
+        self.status_message = format!("正在循环拖拽 - {} ({} 像素/秒)", self.config.mouse_button, self.config.drag_speed);
+    }
+
+    /// 立即重新采样统计，开始运行时不再显示上次运行的数据
+    fn refresh_stats(&mut self) {
+        self.last_stats_update = Instant::now();
+        self.stats = StatsSnapshot::sample(&self.mouse_controller);
+    }
+
+    /// 本次会话结束时把点击数累加到持久统计
+    fn record_session_clicks(&mut self) {
+        // 空跑没有真实点击，不计入统计
+        let clicks = if self.run_mode == RunMode::DryRun {
+            0
+        } else {
+            self.mouse_controller.get_click_count()
+        };
+        self.config.total_clicks += clicks;
+        self.config.clicks_since_reset += clicks.saturating_sub(self.clicks_before_reset);
+        self.clicks_before_reset = 0;
+        let _ = self.config.save();
+    }
+
+    fn stop_clicking(&mut self) {
+        self.mouse_controller.stop_clicking();
+        if self.config.is_running {
+            self.record_session_clicks();
+        }
+        self.config.is_running = false;
+        self.status_message = "已停止".to_string();
+    }
+
+    fn pause_button_ui(&mut self, ui: &mut egui::Ui, size: egui::Vec2) {
+        if self.mouse_controller.is_paused() {
+            if ui.add_sized(size, egui::Button::new("继续")).clicked() {
+                self.mouse_controller.resume();
+                self.status_message = "已继续".to_string();
+            }
+        } else if ui.add_sized(size, egui::Button::new("暂停"))
+            .on_hover_text("暂时停止点击，计数保留，可随时继续")
+            .clicked() {
+            self.mouse_controller.pause();
+            self.status_message = "已暂停".to_string();
+        }
+    }
+
+    /// 被监视的窗口失去前台焦点时停止连点
+    ///
+    /// 绑定了目标窗口时监视目标窗口，否则监视开始连点时的前台窗口。
+    /// 长按模式下开始时前台通常是游戏本身，因此程序窗口最小化也不会误停。
+    fn check_focus_loss(&mut self) {
+        if !self.config.is_running || !self.config.stop_on_focus_loss {
+            self.focus_window = None;
+            return;
+        }
+        let Some(foreground) = target_window::foreground_window() else {
+            return;
+        };
+        let bound_title = self.config.target_window.as_deref().filter(|_| self.config.fixed_position);
+        let watched = match bound_title {
+            Some(title) => target_window::find_window(title),
+            None => Some(*self.focus_window.get_or_insert(foreground)),
+        };
+        if watched != Some(foreground) {
+            self.stop_clicking();
+            self.focus_window = None;
+            self.status_message = "目标窗口失去焦点，已停止".to_string();
+            log_info!("{}", self.status_message);
+        }
+    }
+
+    fn is_hotkey_suspended(&self) -> bool {
+        self.hotkey_manager
+            .as_ref()
+            .is_some_and(|manager| manager.is_suspended())
+    }
+
+    fn handle_tray_events(&mut self, ctx: &egui::Context) {
+        let Some(event) = self.tray_manager.as_ref().and_then(|tray| tray.check_events()) else {
+            return;
+        };
+
+        match event {
+            TrayEvent::ShowWindow => {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                self.config.window_visible = true;
+            }
+            TrayEvent::HideWindow => {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+                self.config.window_visible = false;
+            }
+            TrayEvent::StartClicking => {
+                if self.countdown.is_some() {
+                    self.cancel_countdown();
+                } else if self.config.is_running {
+                    self.stop_clicking();
+                } else {
+                    self.start_clicking(self.config.button_run_mode);
+                }
+            }
+            TrayEvent::StopClicking => {
+                if self.countdown.is_some() {
+                    self.cancel_countdown();
+                }
+                if self.config.is_running {
+                    self.stop_clicking();
+                }
+            }
+            TrayEvent::SwitchProfile(name) => {
+                self.switch_profile(&name);
+            }
+            TrayEvent::Quit => {
+                self.request_quit(ctx);
+            }
+        }
+    }
+
+    /// 托盘“退出程序”：未在连点时直接退出，连点中需要确认，防止误点中断长时间运行
+    fn request_quit(&mut self, ctx: &egui::Context) {
+        if !self.config.is_running && self.countdown.is_none() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            return;
+        }
+
+        if self.config.window_visible {
+            self.show_quit_confirm = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        } else if self.quit_armed_at.is_some_and(|at| at.elapsed() < QUIT_CONFIRM_WINDOW) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        } else {
+            self.quit_armed_at = Some(Instant::now());
+            if let Some(ref tray) = self.tray_manager {
+                tray.set_quit_armed(true);
+            }
+        }
+    }
+
+    /// 二次确认超时后恢复托盘退出菜单项
+    fn expire_quit_confirm(&mut self) {
+        if self.quit_armed_at.is_some_and(|at| at.elapsed() >= QUIT_CONFIRM_WINDOW) {
+            self.quit_armed_at = None;
+            if let Some(ref tray) = self.tray_manager {
+                tray.set_quit_armed(false);
+            }
+        }
+    }
+
+    fn show_quit_confirm_window(&mut self, ctx: &egui::Context) {
+        let mut confirmed = false;
+        let mut cancelled = false;
+
+        egui::Window::new("退出程序")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.label(format!("正在连点（已点击 {} 次），退出会中断本次运行。", self.mouse_controller.get_click_count()));
+                ui.label("确定退出吗？");
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    confirmed = ui.button("退出").clicked();
+                    cancelled = ui.button("取消").clicked();
+                });
+            });
+
+        if confirmed {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+        if confirmed || cancelled {
+            self.show_quit_confirm = false;
+        }
+    }
+
+    /// 配置被整体替换后，同步输入框、热键等派生状态
+    fn reload_config_state(&mut self) {
+        self.interval_input = self.config.click_interval.to_string();
+        self.cps_input = self.config.cps_value.to_string();
+        self.temp_toggle_hotkey = self.config.toggle_hotkey.clone();
+        self.temp_suspend_hotkey = self.config.suspend_hotkey.clone();
+        self.temp_hotkey_bindings = self.config.hotkey_bindings.clone();
+
+        if let Some(ref mut hotkey_manager) = self.hotkey_manager {
+            hotkey_manager.set_revalidate_interval(std::time::Duration::from_secs(self.config.hotkey_revalidate_secs));
+            self.hotkey_error = Self::register_all_hotkeys(hotkey_manager, &self.config).err();
+        }
+    }
+
+    /// 注册切换、挂起及全部额外热键，错误合并后返回
+    fn register_all_hotkeys(manager: &mut HotkeyManager, config: &AppConfig) -> Result<(), String> {
+        let main = manager.update_hotkeys(&config.toggle_hotkey, &config.suspend_hotkey);
+        let bound = manager.update_bindings(&config.hotkey_bindings);
+        match (main, bound) {
+            (Ok(()), Ok(())) => Ok(()),
+            (Err(main), Err(bound)) => Err(format!("{}\n{}", main, bound)),
+            (Err(e), Ok(())) | (Ok(()), Err(e)) => Err(e),
+        }
+    }
+
+    /// 按设置启用或关闭命令文件，并执行读到的命令
+    fn handle_file_commands(&mut self) {
+        if !self.config.command_file_enabled {
+            self.command_file = None;
+            return;
+        }
+        if self.command_file.is_none() {
+            match command_file::command_path() {
+                Ok(path) => self.command_file = Some(CommandFile::new(path)),
+                Err(e) => {
+                    log_error!("命令文件不可用: {}", e);
+                    self.config.command_file_enabled = false;
+                    return;
+                }
+            }
+        }
+
+        let Some(command) = self.command_file.as_mut().and_then(CommandFile::poll) else {
+            return;
+        };
+        log_info!("收到文件命令: {:?}", command);
+        let action = match command {
+            FileCommand::Start => BoundAction::Start,
+            FileCommand::Stop => BoundAction::Stop,
+            FileCommand::Toggle => BoundAction::Toggle,
+        };
+        self.run_bound_action(action);
+    }
+
+    /// 执行额外热键绑定的动作
+    fn run_bound_action(&mut self, action: BoundAction) {
+        match action {
+            BoundAction::Start => {
+                if !self.config.is_running && self.countdown.is_none() {
+                    self.start_clicking(self.config.hotkey_run_mode);
+                }
+            }
+            BoundAction::Stop => {
+                if self.countdown.is_some() {
+                    self.cancel_countdown();
+                } else if self.config.is_running {
+                    self.stop_clicking();
+                }
+            }
+            BoundAction::Toggle => {
+                if self.countdown.is_some() {
+                    self.cancel_countdown();
+                } else if self.config.is_running {
+                    self.stop_clicking();
+                } else {
+                    self.start_clicking(self.config.hotkey_run_mode);
+                }
+            }
+            BoundAction::StartAtCps(cps) => {
+                if self.config.is_running {
+                    self.stop_clicking();
+                }
+                self.countdown = None;
+                self.config.interval_mode = IntervalMode::CPS;
+                self.config.cps_value = cps.max(1);
+                self.cps_input = self.config.cps_value.to_string();
+                self.start_clicking(self.config.hotkey_run_mode);
+            }
+            BoundAction::StartProfile(name) => {
+                self.countdown = None;
+                self.switch_profile(&name);
+                if self.config.active_profile.as_deref() == Some(name.as_str()) {
+                    self.start_clicking(self.config.hotkey_run_mode);
+                }
+            }
+        }
+    }
+
+    fn switch_profile(&mut self, name: &str) {
+        match AppConfig::load_profile(name) {
+            Ok(mut profile) => {
+                if self.config.is_running {
+                    self.stop_clicking();
+                }
+
+                // 累计统计、窗口状态和额外热键不随方案切换
+                profile.is_running = false;
+                profile.total_clicks = self.config.total_clicks;
+                profile.clicks_since_reset = self.config.clicks_since_reset;
+                profile.hotkey_bindings = self.config.hotkey_bindings.clone();
+                profile.window_visible = self.config.window_visible;
+                profile.active_profile = Some(name.to_string());
+                self.config = profile;
+                self.config.select_monitor_profile(&self.monitor_layout);
+                self.reload_config_state();
+                self.profile_name_input = name.to_string();
+
+                let _ = self.config.save();
+                self.status_message = format!("已切换到配置方案: {}", name);
+            }
+            Err(e) => {
+                self.status_message = e;
+            }
+        }
+        self.refresh_profiles();
+    }
+
+    /// 恢复全部设置为默认值，累计点击等统计数据保留
+    fn reset_to_defaults(&mut self) {
+        self.countdown = None;
+        if self.config.is_running {
+            self.stop_clicking();
+        }
+
+        self.config = AppConfig {
+            total_clicks: self.config.total_clicks,
+            clicks_since_reset: self.config.clicks_since_reset,
+            window_visible: self.config.window_visible,
+            last_seen_version: self.config.last_seen_version.take(),
+            first_run_complete: true,
+            ..AppConfig::default()
+        };
+        self.clicks_before_reset = 0;
+
+        self.reload_config_state();
+        self.refresh_profiles();
+        self.status_message = match self.config.save() {
+            Ok(()) => "已恢复默认设置".to_string(),
+            Err(e) => format!("已恢复默认设置，但保存失败: {}", e),
+        };
+        log_info!("{}", self.status_message);
+    }
+
+    fn show_reset_confirm_window(&mut self, ctx: &egui::Context) {
+        let mut confirmed = false;
+        let mut cancelled = false;
+
+        egui::Window::new("恢复默认设置")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.label("所有设置（包括热键和坐标）都将恢复为默认值，累计点击统计会保留。");
+                ui.label("正在进行的连点会先停止。确定继续吗？");
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    confirmed = ui.button("恢复默认").clicked();
+                    cancelled = ui.button("取消").clicked();
+                });
+            });
+
+        if confirmed {
+            self.reset_to_defaults();
+        }
+        if confirmed || cancelled {
+            self.show_reset_confirm = false;
+        }
+    }
+
+    /// 重新读取方案列表并同步到托盘菜单
+    fn refresh_profiles(&mut self) {
+        self.profiles = AppConfig::list_profiles();
+        if let Some(ref mut tray) = self.tray_manager {
+            tray.set_profiles(&self.profiles, self.config.active_profile.as_deref());
+        }
+    }
+
+    /// 状态变化或运行中每秒刷新一次托盘提示
+    fn update_tray_status(&mut self) {
+        let Some(ref tray) = self.tray_manager else {
+            return;
+        };
+
+        let status = if self.is_hotkey_suspended() {
+            "热键已挂起"
+        } else if self.mouse_controller.is_paused() {
+            "已暂停"
+        } else if self.config.is_running {
+            "运行中"
+        } else {
+            "已停止"
+        };
+
+        let now = Instant::now();
+        if status != self.last_tray_status
+            || (self.config.is_running && now.duration_since(self.last_tray_update).as_secs() >= 1)
+        {
+            tray.update_tooltip(status, self.mouse_controller.get_click_count());
+            self.last_tray_status = status.to_string();
+            self.last_tray_update = now;
+        }
+    }
+
+    /// 坐标变化后同步到当前显示器布局并保存
+    fn save_click_points(&mut self) {
+        self.config.store_monitor_profile(&self.monitor_layout);
+        let _ = self.config.save();
+    }
+
+    /// 按拾取网格对齐后添加一个屏幕坐标
+    fn add_screen_point(&mut self, point: ClickPoint) {
+        // 屏幕坐标与窗口相对坐标不能混用，切换时清空已有坐标
+        if self.config.target_window.take().is_some() {
+            self.config.click_points.clear();
+        }
+        let point = point.snapped(self.config.capture_grid);
+        self.config.click_points.push(point);
+        self.save_click_points();
+        self.status_message = format!("已拾取坐标 ({}, {})", point.x, point.y);
+    }
+
+    /// 拾取倒计时结束时记录光标位置
+    fn update_capture(&mut self) {
+        if let Some(deadline) = self.capture_deadline
+            && Instant::now() >= deadline
+        {
+            self.capture_deadline = None;
+            let Some(point) = MouseController::cursor_position() else {
+                self.status_message = "获取光标位置失败".to_string();
+                return;
+            };
+            let grid = self.config.capture_grid;
+
+            if !self.capture_relative {
+                self.add_screen_point(point);
+                return;
+            }
+
+            match target_window::window_at(point) {
+                Some((title, relative)) => {
+                    let relative = relative.snapped(grid);
+                    if self.config.target_window.as_deref() != Some(title.as_str()) {
+                        self.config.click_points.clear();
+                        self.config.target_window = Some(title.clone());
+                    }
+                    self.config.click_points.push(relative);
+                    self.save_click_points();
+                    self.status_message = format!("已拾取窗口「{}」内坐标 ({}, {})", title, relative.x, relative.y);
+                }
+                None => {
+                    self.status_message = "光标下没有可识别的窗口".to_string();
+                }
+            }
+        }
+    }
+
+    /// 时:分 编辑控件，返回是否修改
+    fn time_of_day_ui(ui: &mut egui::Ui, time: &mut TimeOfDay) -> bool {
+        let mut changed = ui.add(egui::DragValue::new(&mut time.hour).clamp_range(0..=23)).changed();
+        ui.label(":");
+        changed |= ui.add(egui::DragValue::new(&mut time.minute).clamp_range(0..=59)).changed();
+        changed
+    }
+
+    /// 判断设置项的关键字是否匹配搜索内容（filter 需已转为小写）
+    fn setting_matches(filter: &str, keywords: &str) -> bool {
+        filter.is_empty() || keywords.to_lowercase().contains(filter)
+    }
+}
+
+impl eframe::App for AutoClickerApp {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        // 提高热键检查频率，确保响应及时
+        let now = Instant::now();
+        if now.duration_since(self.last_update) >= std::time::Duration::from_millis(10) {
+            self.handle_hotkey_events();
+            self.last_update = now;
+        }
+
+        self.update_capture();
+        self.update_countdown(ctx);
+        self.sync_click_hook();
+        self.check_focus_loss();
+        self.handle_file_commands();
+        self.poll_benchmark();
+        self.handle_tray_events(ctx);
+        self.expire_quit_confirm();
+
+        // 点击线程自行退出（如注入失败）时同步界面状态
+        if self.config.is_running && !self.mouse_controller.is_running() {
+            self.mouse_controller.stop_clicking();
+            self.record_session_clicks();
+            self.config.is_running = false;
+            self.status_message = if self.mouse_controller.injection_failed() {
+                if input_backend::is_remote_session() {
+                    "无法注入鼠标事件（远程会话中，点击可能无效）".to_string()
+                } else {
+                    "无法注入鼠标事件（输入桌面可能已切换，如锁屏或权限提升窗口）".to_string()
+                }
+            } else if self.run_mode == RunMode::SingleShot {
+                "已完成单次点击".to_string()
+            } else if self.run_mode == RunMode::RhythmTest && self.mouse_controller.limit_reached() {
+                "节奏测试完成".to_string()
+            } else if self.mouse_controller.stopped_by_quiet_hours() {
+                format!("已进入禁止时段 {}，已停止", self.config.quiet_hours)
+            } else if self.mouse_controller.limit_reached() {
+                format!("已达到点击上限 {} 次", self.config.click_limit)
+            } else {
+                "已停止".to_string()
+            };
+            if self.mouse_controller.injection_failed() {
+                log_error!("{}", self.status_message);
+            } else {
+                log_info!("{}", self.status_message);
+            }
+
+            // 单次点击和节奏测试使用内置上限，不执行配置中达到上限后的动作
+            if self.mouse_controller.limit_reached()
+                && !matches!(self.run_mode, RunMode::SingleShot | RunMode::RhythmTest)
+                && let LimitAction::SwitchProfile(name) = self.config.on_limit.clone()
+            {
+                self.switch_profile(&name);
+                if self.config.active_profile.as_deref() == Some(name.as_str()) {
+                    self.start_clicking(self.run_mode);
+                }
+            }
+        }
+
+        // 运行中按设置的间隔采样统计，停止后始终显示最终数据
+        let stats_refresh = std::time::Duration::from_millis(self.config.stats_refresh_ms.clamp(100, 2000));
+        if !self.config.is_running || now.duration_since(self.last_stats_update) >= stats_refresh {
+            self.refresh_stats();
+        }
+
+        self.update_tray_status();
+
+        // 只初始化一次样式，避免每帧都克隆
+        if !self.style_initialized {
+            let mut style = (*ctx.style()).clone();
+            style.spacing.button_padding = egui::vec2(12.0, 8.0);
+            style.spacing.item_spacing = egui::vec2(8.0, 8.0);
+            ctx.set_style(style);
+            self.style_initialized = true;
+        }
+
+        // 菜单栏
+        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| {
+                ui.menu_button("工具", |ui| {
+                    if ui.button("重新加载字体").on_hover_text("安装中文字体后无需重启即可生效").clicked() {
+                        self.status_message = match crate::setup_fonts(ctx) {
+                            Some(path) => format!("已重新加载字体: {}", path),
+                            None => "未找到可用的中文字体".to_string(),
+                        };
+                        ui.close_menu();
+                    }
+                    if ui.button("更新说明").on_hover_text("查看各版本的更新内容").clicked() {
+                        self.whats_new = changelog::RELEASES.iter().collect();
+                        ui.close_menu();
+                    }
+                    if ui.button("日志").on_hover_text("查看最近的运行日志").clicked() {
+                        self.show_log_viewer = true;
+                        self.refresh_log_lines();
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(!self.config.is_running && self.countdown.is_none(), egui::Button::new("节奏测试"))
+                        .on_hover_text("3 秒后按“三快一慢”的节奏点击四组，可在画图程序中确认点击位置和间隔")
+                        .clicked() {
+                        self.begin_countdown(3, RunMode::RhythmTest);
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(!self.config.is_running && self.benchmark_rx.is_none(), egui::Button::new("性能测试"))
+                        .on_hover_text("空跑测量本机可达到的最高点击频率")
+                        .clicked() {
+                        self.show_benchmark = true;
+                        self.start_benchmark();
+                        ui.close_menu();
+                    }
+                });
+            });
+        });
+
+        // 主窗口UI
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(10.0);
+                ui.heading("鼠标连点器");
+                ui.add_space(5.0);
+                ui.label("高效、简洁的自动点击工具");
+            });
+
+            ui.separator();
+            ui.add_space(10.0);
+
+            // 设置搜索
+            ui.horizontal(|ui| {
+                ui.label("搜索设置:");
+                ui.add(egui::TextEdit::singleline(&mut self.settings_filter)
+                    .hint_text("输入关键字筛选选项")
+                    .desired_width(200.0));
+                if !self.settings_filter.is_empty() && ui.small_button("清除").clicked() {
+                    self.settings_filter.clear();
+                }
+            });
+            let filter = self.settings_filter.trim().to_lowercase();
+
+            ui.add_space(5.0);
+
+            // 主要控制区域
+            ui.group(|ui| {
+                ui.set_min_width(350.0);
+
+                if Self::setting_matches(&filter, "配置方案 方案 保存 删除 切换") {
+                    // 配置方案
+                    let mut switch_to = None;
+                    ui.horizontal(|ui| {
+                        ui.label("配置方案:");
+                        let selected = self.config.active_profile.clone().unwrap_or_else(|| "未选择".to_string());
+                        egui::ComboBox::from_id_source("profile_select")
+                            .selected_text(selected)
+                            .width(120.0)
+                            .show_ui(ui, |ui| {
+                                for name in &self.profiles {
+                                    let active = self.config.active_profile.as_deref() == Some(name.as_str());
+                                    if ui.selectable_label(active, name).clicked() && !active {
+                                        switch_to = Some(name.clone());
+                                    }
+                                }
+                            });
+
+                        if let Some(ref active) = self.config.active_profile
+                            && ui.small_button("删除").clicked()
+                        {
+                            match AppConfig::delete_profile(active) {
+                                Ok(()) => {
+                                    self.status_message = format!("已删除配置方案: {}", active);
+                                    self.config.active_profile = None;
+                                    let _ = self.config.save();
+                                }
+                                Err(e) => self.status_message = e,
+                            }
+                            self.refresh_profiles();
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("保存为:");
+                        ui.add(egui::TextEdit::singleline(&mut self.profile_name_input)
+                            .hint_text("方案名称")
+                            .desired_width(120.0));
+                        if ui.small_button("保存").clicked() {
+                            let name = self.profile_name_input.trim().to_string();
+                            self.config.active_profile = Some(name.clone());
+                            match self.config.save_profile(&name) {
+                                Ok(()) => {
+                                    self.status_message = format!("已保存配置方案: {}", name);
+                                    let _ = self.config.save();
+                                }
+                                Err(e) => {
+                                    self.config.active_profile = None;
+                                    self.status_message = e;
+                                }
+                            }
+                            self.refresh_profiles();
+                        }
+                    });
+                    if let Some(name) = switch_to {
+                        self.switch_profile(&name);
+                    }
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "鼠标按键 左键 右键") {
+                    // 鼠标按键选择
+                    ui.horizontal(|ui| {
+                        ui.label("鼠标按键:");
+                        if ui.radio_value(&mut self.config.mouse_button, MouseButton::Left, "左键").changed() {
+                            let _ = self.config.save();
+                        }
+                        if ui.radio_value(&mut self.config.mouse_button, MouseButton::Right, "右键").changed() {
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "间隔模式 毫秒 CPS") {
+                    // 间隔模式选择
+                    ui.horizontal(|ui| {
+                        ui.label("间隔模式:");
+                        if ui.radio_value(&mut self.config.interval_mode, IntervalMode::Milliseconds, "毫秒").changed() {
+                            let _ = self.config.save();
+                        }
+                        if ui.radio_value(&mut self.config.interval_mode, IntervalMode::CPS, "CPS").changed() {
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.add_space(5.0);
+                }
+
+                if Self::setting_matches(&filter, "点击间隔 点击频率 CPS 快速 中等 慢速 刷新率") {
+                    // 间隔时间设置
+                    match self.config.interval_mode {
+                        IntervalMode::Milliseconds => {
+                            ui.horizontal(|ui| {
+                                ui.label("点击间隔:");
+                                if ui.add(egui::DragValue::new(&mut self.config.click_interval)
+                                    .speed(1.0)
+                                    .clamp_range(1..=10000)
+                                    .suffix(" ms")).changed() {
+                                    let _ = self.config.save();
+                                    self.interval_input = self.config.click_interval.to_string();
+                                }
+
+                                if ui.small_button("快速").clicked() {
+                                    self.config.click_interval = 50;
+                                    self.interval_input = "50".to_string();
+                                    let _ = self.config.save();
+                                    self.cps_input = self.config.cps_value.to_string();
+                                }
+                                if ui.small_button("中等").clicked() {
+                                    self.config.click_interval = 100;
+                                    self.interval_input = "100".to_string();
+                                    let _ = self.config.save();
+                                }
+                                if ui.small_button("慢速").clicked() {
+                                    self.config.click_interval = 500;
+                                    self.interval_input = "500".to_string();
+                                    let _ = self.config.save();
+                                }
+                            });
+                        }
+                        IntervalMode::CPS => {
+                            ui.horizontal(|ui| {
+                                ui.label("点击频率:");
+                                if ui.add(egui::DragValue::new(&mut self.config.cps_value)
+                                    .speed(1.0)
+                                    .clamp_range(1..=1000)
+                                    .suffix(" CPS")).changed() {
+                                    let _ = self.config.save();
+                                }
+
+                                if ui.small_button("慢速").clicked() {
+                                    self.config.cps_value = 2;
+                                    self.cps_input = "2".to_string();
+                                    let _ = self.config.save();
+                                }
+                                if ui.small_button("中等").clicked() {
+                                    self.config.cps_value = 10;
+                                    self.cps_input = "10".to_string();
+                                    let _ = self.config.save();
+                                }
+                                if ui.small_button("快速").clicked() {
+                                    self.config.cps_value = 20;
+                                    self.cps_input = "20".to_string();
+                                    let _ = self.config.save();
+                                }
+                            });
+                        
+                            // 高 CPS 警告
+                            if !self.config.suppress_cps_warning
+                                && self.config.cps_value > self.config.cps_warning_threshold {
+                                ui.horizontal(|ui| {
+                                    ui.colored_label(
+                                        egui::Color32::from_rgb(255, 165, 0),
+                                        format!("⚠ 高频率 ({} CPS) 可能影响系统性能", self.config.cps_value)
+                                    );
+                                    if ui.small_button("不再提示").clicked() {
+                                        self.config.suppress_cps_warning = true;
+                                        let _ = self.config.save();
+                                    }
+                                });
+                            }
+                        }
+                    }
+
+                    // 刷新率上限
+                    ui.horizontal(|ui| {
+                        match self.refresh_rate {
+                            Some(hz) => {
+                                if ui.checkbox(&mut self.config.cap_to_refresh_rate, format!("不超过刷新率 ({} Hz)", hz))
+                                    .on_hover_text("只在每帧读取一次输入的游戏中，超过刷新率的点击没有意义")
+                                    .changed() {
+                                    let _ = self.config.save();
+                                }
+                            }
+                            None => {
+                                ui.add_enabled(false, egui::Checkbox::new(&mut self.config.cap_to_refresh_rate, "不超过刷新率"))
+                                    .on_disabled_hover_text("未能检测到显示器刷新率");
+                            }
+                        }
+                    });
+
+                    // 线程数说明
+                    let effective_interval = self.config.capped_interval(self.refresh_rate);
+                    let (threads, reason) = MouseController::thread_plan(effective_interval);
+                    let effective_cps = 1000.0 / effective_interval.max(1) as f64;
+                    ui.weak(format!("实际间隔 {}ms ≈ {:.0} CPS，工作线程: {}", effective_interval, effective_cps, threads))
+                        .on_hover_text(reason);
+
+                    // 按住预览：空跑当前设置并显示实测频率
+                    ui.horizontal(|ui| {
+                        let response = ui.add(egui::Button::new("按住预览").sense(egui::Sense::click_and_drag()))
+                            .on_hover_text("按住时以当前设置空跑（不会真实点击），松开停止");
+                        if response.is_pointer_button_down_on() {
+                            let preview = self.speed_preview.get_or_insert_with(|| {
+                                let mut controller = MouseController::with_backend(Arc::new(DryRunBackend::new()));
+                                controller.start_clicking(ClickSettings::new(self.config.mouse_button, effective_interval));
+                                controller
+                            });
+                            ui.label(format!("实测 {:.1} CPS", preview.get_cps()));
+                        } else if let Some(mut preview) = self.speed_preview.take() {
+                            preview.stop_clicking();
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "点击上限 次数 限制 归零 切换方案") {
+                    // 点击次数上限
+                    ui.horizontal(|ui| {
+                        ui.label("点击上限:");
+                        if ui.add(egui::DragValue::new(&mut self.config.click_limit)
+                            .speed(1.0)
+                            .suffix(" 次"))
+                            .on_hover_text("0 表示不限制")
+                            .changed() {
+                            let _ = self.config.save();
+                        }
+
+                        ui.add_enabled_ui(self.config.click_limit > 0, |ui| {
+                            ui.label("达到后:");
+                            let selected = match &self.config.on_limit {
+                                LimitAction::Stop => "停止".to_string(),
+                                LimitAction::Restart => "归零继续".to_string(),
+                                LimitAction::SwitchProfile(name) => format!("切换到 {}", name),
+                            };
+                            let mut changed = false;
+                            egui::ComboBox::from_id_source("on_limit")
+                                .selected_text(selected)
+                                .show_ui(ui, |ui| {
+                                    changed |= ui.selectable_value(&mut self.config.on_limit, LimitAction::Stop, "停止").changed();
+                                    changed |= ui.selectable_value(&mut self.config.on_limit, LimitAction::Restart, "归零继续").changed();
+                                    for name in &self.profiles {
+                                        changed |= ui.selectable_value(
+                                            &mut self.config.on_limit,
+                                            LimitAction::SwitchProfile(name.clone()),
+                                            format!("切换到 {}", name),
+                                        ).changed();
+                                    }
+                                });
+                            if changed {
+                                let _ = self.config.save();
+                            }
+                        });
+                    });
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "点击节奏 节奏 跳过 停顿") {
+                    self.click_pattern_ui(ui);
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "触发模式 长按触发 切换") {
+                    // 触发模式设置
+                    ui.horizontal(|ui| {
+                        ui.label("触发模式:");
+                        if ui.checkbox(&mut self.config.hold_mode, "长按触发").changed() {
+                            // 切换模式时重置热键状态
+                            if let Some(ref mut hotkey_manager) = self.hotkey_manager {
+                                hotkey_manager.reset_key_state();
+                                let _ = hotkey_manager.update_hotkeys(&self.config.toggle_hotkey, &self.config.suspend_hotkey);
+                            }
+                            let _ = self.config.save();
+                        }
+                        ui.label(if self.config.hold_mode {
+                            "(按住热键连点，松开停止)"
+                        } else {
+                            "(按一次开始，再按一次停止)"
+                        });
+                    });
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "点击位置 当前光标 固定坐标 拾取 上次点击 显示器 相对窗口 网格 对齐 移动事件 兼容 点击顺序 随机 平滑移动 曲线") {
+                    // 点击位置设置
+                    ui.horizontal(|ui| {
+                        ui.label("点击位置:");
+                        if ui.radio_value(&mut self.config.fixed_position, false, "当前光标").changed() {
+                            let _ = self.config.save();
+                        }
+                        if ui.radio_value(&mut self.config.fixed_position, true, "固定坐标").changed() {
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    if !self.config.fixed_position {
+                        ui.horizontal(|ui| {
+                            if ui.checkbox(&mut self.config.reassert_position, "附带移动事件")
+                                .on_hover_text("每次点击前在当前位置发送一次绝对移动事件。部分游戏只响应带移动的点击，默认只发送按键事件")
+                                .changed() {
+                                let _ = self.config.save();
+                            }
+                        });
+                    }
+
+                    if self.config.fixed_position {
+                        let mut points_changed = false;
+                        let mut remove_index = None;
+                        if let Some(title) = self.config.target_window.clone() {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("相对窗口: {}", title));
+                                if ui.small_button("改用屏幕坐标")
+                                    .on_hover_text("清除目标窗口，坐标按屏幕坐标处理")
+                                    .clicked() {
+                                    self.config.target_window = None;
+                                    points_changed = true;
+                                }
+                            });
+                        }
+                        for (i, point) in self.config.click_points.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("坐标{}:", i + 1));
+                                points_changed |= ui.add(egui::DragValue::new(&mut point.x).prefix("X: ")).changed();
+                                points_changed |= ui.add(egui::DragValue::new(&mut point.y).prefix("Y: ")).changed();
+                                if ui.small_button("删除").clicked() {
+                                    remove_index = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = remove_index {
+                            self.config.click_points.remove(i);
+                            points_changed = true;
+                        }
+
+                        ui.horizontal(|ui| {
+                            if let Some(deadline) = self.capture_deadline {
+                                let remaining = deadline.saturating_duration_since(Instant::now());
+                                ui.label(format!("请将光标移到目标位置... {:.0}秒", remaining.as_secs_f64().ceil()));
+                                if ui.small_button("取消").clicked() {
+                                    self.capture_deadline = None;
+                                }
+                            } else {
+                                if ui.small_button("拾取 (3秒后)")
+                                    .on_hover_text("3秒后记录光标所在位置")
+                                    .clicked() {
+                                    self.capture_relative = false;
+                                    self.capture_deadline = Some(Instant::now() + std::time::Duration::from_secs(3));
+                                }
+                                if ui.small_button("相对窗口拾取")
+                                    .on_hover_text("3秒后记录光标相对其所在窗口的位置，窗口移动后仍能点中")
+                                    .clicked() {
+                                    self.capture_relative = true;
+                                    self.capture_deadline = Some(Instant::now() + std::time::Duration::from_secs(3));
+                                }
+                                let last_click = click_hook::last_click();
+                                let hover = last_click
+                                    .map(|point| format!("添加最近一次在其他程序中点击的位置 ({}, {})", point.x, point.y))
+                                    .unwrap_or_default();
+                                if ui.add_enabled(last_click.is_some(), egui::Button::new("上次点击位置").small())
+                                    .on_hover_text(hover)
+                                    .on_disabled_hover_text("尚未记录到点击，先在目标位置点击一次")
+                                    .clicked()
+                                    && let Some(point) = last_click
+                                {
+                                    self.add_screen_point(point);
+                                }
+                                if ui.small_button("添加").clicked() {
+                                    self.config.click_points.push(ClickPoint::default());
+                                    points_changed = true;
+                                }
+                            }
+                        });
+
+                        if self.config.target_window.is_none() {
+                            ui.label(format!(
+                                "当前显示器布局: {} 个显示器（坐标按布局分别保存）",
+                                monitor::monitor_count(&self.monitor_layout)
+                            ));
+                        }
+
+                        if self.config.click_points.len() > 1 {
+                            ui.horizontal(|ui| {
+                                ui.label("点击顺序:");
+                                let mut changed = false;
+                                egui::ComboBox::from_id_source("point_order")
+                                    .selected_text(self.config.point_order.to_string())
+                                    .show_ui(ui, |ui| {
+                                        for order in [PointOrder::Sequential, PointOrder::Reverse, PointOrder::Random] {
+                                            changed |= ui.selectable_value(&mut self.config.point_order, order, order.to_string()).changed();
+                                        }
+                                    })
+                                    .response
+                                    .on_hover_text("随机：每一轮打乱坐标顺序，每个坐标仍各点一次");
+                                if changed {
+                                    let _ = self.config.save();
+                                }
+                            });
+                        }
+
+                        ui.horizontal(|ui| {
+                            let mut changed = ui.checkbox(&mut self.config.glide_between_points, "平滑移动")
+                                .on_hover_text("点击前沿随机弯曲的曲线把光标移到坐标，而不是直接跳过去。\n移动耗时不超过点击间隔的一半，间隔小于 20ms 时不生效")
+                                .changed();
+                            ui.add_enabled_ui(self.config.glide_between_points, |ui| {
+                                changed |= ui.add(egui::DragValue::new(&mut self.config.glide_speed)
+                                    .speed(20.0)
+                                    .clamp_range(100..=20000)
+                                    .suffix(" 像素/秒"))
+                                    .changed();
+                            });
+                            if changed {
+                                let _ = self.config.save();
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label("对齐网格:");
+                            if ui.add(egui::DragValue::new(&mut self.config.capture_grid)
+                                .speed(0.2)
+                                .clamp_range(0..=100)
+                                .suffix(" 像素"))
+                                .on_hover_text("拾取时把坐标对齐到最近的网格点，减少手抖造成的偏差，0 表示不对齐")
+                                .changed() {
+                                let _ = self.config.save();
+                            }
+                        });
+
+                        if points_changed {
+                            self.save_click_points();
+                        }
+                    }
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "启动方式 空跑 单次 开始按钮 热键 测试") {
+                    // 开始按钮与热键分别的启动方式
+                    ui.horizontal(|ui| {
+                        ui.label("启动方式:");
+                        let mut changed = false;
+                        for (label, id, mode) in [
+                            ("按钮", "button_run_mode", &mut self.config.button_run_mode),
+                            ("热键", "hotkey_run_mode", &mut self.config.hotkey_run_mode),
+                        ] {
+                            ui.label(label);
+                            egui::ComboBox::from_id_source(id)
+                                .selected_text(mode.to_string())
+                                .show_ui(ui, |ui| {
+                                    for option in [RunMode::Real, RunMode::DryRun, RunMode::SingleShot, RunMode::RhythmTest] {
+                                        changed |= ui.selectable_value(mode, option, option.to_string()).changed();
+                                    }
+                                });
+                        }
+                        if changed {
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "拟人 抖动 随机 偏移 按住时长 间隔浮动") {
+                    ui.horizontal(|ui| {
+                        let mut humanize = self.config.humanize_preset_active();
+                        if ui.checkbox(&mut humanize, "拟人")
+                            .on_hover_text("一键设置合理的位置偏移、按住时长和间隔浮动，之后仍可单独调整下面各项")
+                            .changed() {
+                            self.config.apply_humanize_preset(humanize);
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("位置偏移:");
+                        let mut changed = ui.add(egui::DragValue::new(&mut self.config.position_jitter_px)
+                            .clamp_range(0..=50)
+                            .prefix("±")
+                            .suffix(" px"))
+                            .on_hover_text("仅对固定坐标生效，每次点击在坐标周围随机偏移")
+                            .changed();
+                        ui.label("间隔浮动:");
+                        changed |= ui.add(egui::DragValue::new(&mut self.config.interval_jitter_pct)
+                            .clamp_range(0..=50)
+                            .prefix("±")
+                            .suffix(" %"))
+                            .changed();
+                        if changed {
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("按住时长:");
+                        let mut changed = ui.add(egui::DragValue::new(&mut self.config.hold_min_ms)
+                            .clamp_range(0..=1000)
+                            .suffix(" ms"))
+                            .changed();
+                        ui.label("-");
+                        changed |= ui.add(egui::DragValue::new(&mut self.config.hold_max_ms)
+                            .clamp_range(0..=1000)
+                            .suffix(" ms"))
+                            .on_hover_text("每次点击按下后随机保持一段时间再松开，最长不超过点击间隔的一半")
+                            .changed();
+                        if changed {
+                            self.config.hold_max_ms = self.config.hold_max_ms.max(self.config.hold_min_ms);
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "随机延迟 焦点 失去焦点 禁止时段 定时") {
+                    ui.horizontal(|ui| {
+                        ui.label("首次点击随机延迟:");
+                        let mut changed = ui.add(egui::DragValue::new(&mut self.config.start_jitter_min_ms)
+                            .speed(5.0)
+                            .clamp_range(0..=5000)
+                            .suffix(" ms"))
+                            .changed();
+                        ui.label("-");
+                        changed |= ui.add(egui::DragValue::new(&mut self.config.start_jitter_max_ms)
+                            .speed(5.0)
+                            .clamp_range(0..=5000)
+                            .suffix(" ms"))
+                            .on_hover_text("按下热键后随机等待一段时间再点击第一次，避免按下瞬间就点击。期间松开长按热键则不会点击")
+                            .changed();
+                        if changed {
+                            self.config.start_jitter_max_ms = self.config.start_jitter_max_ms.max(self.config.start_jitter_min_ms);
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        let mut changed = ui.checkbox(&mut self.config.quiet_hours_enabled, "禁止时段")
+                            .on_hover_text("该时段内不会开始连点，运行中进入该时段会自动停止。结束早于开始表示跨越午夜")
+                            .changed();
+                        ui.add_enabled_ui(self.config.quiet_hours_enabled, |ui| {
+                            changed |= Self::time_of_day_ui(ui, &mut self.config.quiet_hours.start);
+                            ui.label("至");
+                            changed |= Self::time_of_day_ui(ui, &mut self.config.quiet_hours.end);
+                        });
+                        if changed {
+                            let _ = self.config.save();
+                        }
+                        if self.config.quiet_hours_enabled {
+                            if self.config.quiet_hours.contains(TimeOfDay::now()) {
+                                ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "当前禁止");
+                            } else {
+                                ui.colored_label(egui::Color32::from_rgb(0, 150, 0), "当前允许");
+                            }
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        if ui.checkbox(&mut self.config.stop_on_focus_loss, "失去焦点时停止")
+                            .on_hover_text("开始连点时的前台窗口（绑定目标窗口时为目标窗口）切到后台或关闭后自动停止")
+                            .changed() {
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "高频警告 CPS 警告阈值 性能") {
+                    // 高频警告设置
+                    ui.horizontal(|ui| {
+                        ui.label("高频警告:");
+                        let mut show_warning = !self.config.suppress_cps_warning;
+                        if ui.checkbox(&mut show_warning, "显示").changed() {
+                            self.config.suppress_cps_warning = !show_warning;
+                            let _ = self.config.save();
+                        }
+                        ui.add_enabled_ui(show_warning, |ui| {
+                            ui.label("阈值:");
+                            if ui.add(egui::DragValue::new(&mut self.config.cps_warning_threshold)
+                                .speed(1.0)
+                                .clamp_range(1..=1000)
+                                .suffix(" CPS")).changed() {
+                                let _ = self.config.save();
+                            }
+                        });
+                    });
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "统计刷新 统计 刷新间隔 更新频率 暂停 计时") {
+                    ui.horizontal(|ui| {
+                        ui.label("统计刷新:");
+                        if ui.add(egui::DragValue::new(&mut self.config.stats_refresh_ms)
+                            .speed(10.0)
+                            .clamp_range(100..=2000)
+                            .suffix(" ms"))
+                            .on_hover_text("运行中统计数字的更新间隔，越小越实时，越大越省资源")
+                            .changed() {
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        if ui.checkbox(&mut self.config.pause_freezes_stats, "暂停时停止计时")
+                            .on_hover_text("开启时运行时间和 CPS 不包含暂停的时长；关闭时暂停期间时间照常累计")
+                            .changed() {
+                            self.mouse_controller.set_pause_freezes_stats(self.config.pause_freezes_stats);
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "省电模式 电池 笔记本 重绘 唤醒") {
+                    ui.horizontal(|ui| {
+                        ui.label("省电模式:");
+                        if ui.checkbox(&mut self.config.power_saving, "隐藏时降低唤醒频率")
+                            .on_hover_text("窗口隐藏到托盘且未在连点时，每秒唤醒从 10 次降到 2 次。\n代价是切换热键最多延迟约 0.5 秒才响应；长按模式不受影响")
+                            .changed() {
+                            let _ = self.config.save();
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                }
+
+                if Self::setting_matches(&filter, "命令文件 脚本 批处理 外部控制") {
+                    ui.horizontal(|ui| {
+                        ui.label("命令文件:");
+                        let path = command_file::command_path()
+                            .map(|path| path.display().to_string())
+                            .unwrap_or_default();
+                        if ui.checkbox(&mut self.config.command_file_enabled, "允许脚本控制")
+                            .on_hover_text(format!("向 {} 写入 start、stop 或 toggle 即可控制连点，读取后文件会被清空", path))
+                            .changed() {
+                            let _ = self.config.save();
                         }
-                    }
+                    });
+
+                    ui.add_space(8.0);
                 }
 
-                ui.add_space(8.0);
+                if Self::setting_matches(&filter, "宏模式 宏 按键 编辑宏") {
+                    // 宏模式设置
+                    ui.horizontal(|ui| {
+                        ui.label("宏模式:");
+                        if ui.checkbox(&mut self.config.macro_mode, "循环执行宏")
+                            .on_hover_text("开始后按顺序循环执行宏步骤，代替普通连点")
+                            .changed() {
+                            let _ = self.config.save();
+                        }
+                        if ui.small_button("编辑宏").clicked() {
+                            self.show_macro_editor = true;
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                }
 
-                // 触发模式设置
-                ui.horizontal(|ui| {
-                    ui.label("触发模式:");
-                    if ui.checkbox(&mut self.config.hold_mode, "长按触发").changed() {
-                        // 切换模式时重置热键状态
-                        if let Some(ref mut hotkey_manager) = self.hotkey_manager {
-                            hotkey_manager.reset_key_state();
-                            let _ = hotkey_manager.update_hotkeys(&self.config.toggle_hotkey);
+                if Self::setting_matches(&filter, "拖拽模式 拖动 起点 终点 速度") {
+                    // 拖拽模式设置
+                    ui.horizontal(|ui| {
+                        ui.label("拖拽模式:");
+                        if ui.checkbox(&mut self.config.drag_mode, "循环拖拽")
+                            .on_hover_text("按下鼠标键从起点移动到终点后松开，间隔沿用点击间隔")
+                            .changed() {
+                            let _ = self.config.save();
                         }
-                        let _ = self.config.save();
-                    }
-                    ui.label(if self.config.hold_mode {
-                        "(按住热键连点，松开停止)"
-                    } else {
-                        "(按一次开始，再按一次停止)"
                     });
-                });
 
-                ui.add_space(8.0);
+                    if self.config.drag_mode {
+                        if self.config.drag_path.len() < 2 {
+                            self.config.drag_path.resize(2, ClickPoint::default());
+                        }
+                        let mut drag_changed = false;
+                        for (i, label) in ["起点:", "终点:"].iter().enumerate() {
+                            let point = &mut self.config.drag_path[i];
+                            ui.horizontal(|ui| {
+                                ui.label(*label);
+                                drag_changed |= ui.add(egui::DragValue::new(&mut point.x).prefix("X: ")).changed();
+                                drag_changed |= ui.add(egui::DragValue::new(&mut point.y).prefix("Y: ")).changed();
+                            });
+                        }
+                        ui.horizontal(|ui| {
+                            ui.label("速度:");
+                            drag_changed |= ui.add(egui::DragValue::new(&mut self.config.drag_speed)
+                                .speed(10.0)
+                                .clamp_range(1..=20000)
+                                .suffix(" 像素/秒")).changed();
+                        });
+                        if drag_changed {
+                            let _ = self.config.save();
+                        }
+                    }
+
+                    ui.add_space(8.0);
+                }
 
                 // 控制按钮
                 ui.horizontal(|ui| {
                     let button_size = egui::vec2(100.0, 35.0);
 
-                    if self.config.is_running {
+                    if self.countdown.is_some() {
+                        if ui.add_sized(button_size, egui::Button::new("取消"))
+                            .on_hover_text("取消倒计时")
+                            .clicked() {
+                            self.cancel_countdown();
+                        }
+                    } else if self.config.is_running {
                         if ui.add_sized(button_size, egui::Button::new("停止"))
                             .on_hover_text("停止自动点击")
                             .clicked() {
                             self.stop_clicking();
                         }
+                        // 暂停只作用于连点，宏和拖拽直接停止
+                        if !self.config.macro_mode && !self.config.drag_mode {
+                            ui.add_space(10.0);
+                            self.pause_button_ui(ui, button_size);
+                        }
                     } else {
                         if ui.add_sized(button_size, egui::Button::new("开始"))
-                            .on_hover_text("开始自动点击")
+                            .on_hover_text(format!("开始自动点击（{}）", self.config.button_run_mode))
                             .clicked() {
-                            self.start_clicking();
+                            self.start_clicking(self.config.button_run_mode);
                         }
                     }
 
@@ -316,6 +2028,14 @@ impl eframe::App for AutoClickerApp {
                         self.show_hotkey_settings = true;
                     }
                 });
+
+                ui.horizontal(|ui| {
+                    if ui.small_button("恢复默认设置")
+                        .on_hover_text("将所有设置恢复为默认值，累计点击统计保留")
+                        .clicked() {
+                        self.show_reset_confirm = true;
+                    }
+                });
             });
 
             ui.add_space(10.0);
@@ -335,21 +2055,69 @@ impl eframe::App for AutoClickerApp {
                 });
 
                 ui.horizontal(|ui| {
-                    ui.label("总点击次数:");
-                    ui.label(format!("{}", self.mouse_controller.get_click_count()));
+                    ui.label("消息:");
+                    ui.label(&self.status_message);
+                });
+
+                // 运行中的点击尚未累加到持久统计，空跑的点击不计入
+                let session_clicks = self.stats.clicks;
+                let pending = if self.config.is_running && self.run_mode != RunMode::DryRun {
+                    session_clicks
+                } else {
+                    0
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label("本次点击:");
+                    ui.label(format!("{}", session_clicks));
+                    if self.config.is_running && self.config.click_limit > 0 {
+                        let progress = if self.config.on_limit == LimitAction::Restart {
+                            session_clicks % self.config.click_limit
+                        } else {
+                            session_clicks
+                        };
+                        ui.weak(format!("(本轮 {}/{})", progress, self.config.click_limit));
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("重置后点击:");
+                    ui.label(format!("{}", (self.config.clicks_since_reset + pending).saturating_sub(self.clicks_before_reset)));
+                    if ui.small_button("重置")
+                        .on_hover_text("只清零此计数，不影响累计点击")
+                        .clicked() {
+                        // 运行中重置时，本次会话已有的点击不计入
+                        self.config.clicks_since_reset = 0;
+                        self.clicks_before_reset = pending;
+                        let _ = self.config.save();
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("累计点击:");
+                    ui.label(format!("{}", self.config.total_clicks + pending));
                 });
 
                 if self.config.is_running {
                     ui.horizontal(|ui| {
                         ui.label("运行时间:");
-                        if let Some(duration) = self.mouse_controller.get_running_time() {
+                        if let Some(duration) = self.stats.running_time {
                             ui.label(format!("{:.1}秒", duration.as_secs_f64()));
                         }
                     });
 
                     ui.horizontal(|ui| {
                         ui.label("点击频率:");
-                        ui.label(format!("{:.1} 次/秒", self.mouse_controller.get_cps()));
+                        ui.label(format!("{:.1} 次/秒", self.stats.cps));
+                        if self.mouse_controller.throttled() {
+                            ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "已自动降速")
+                                .on_hover_text("系统输入队列已满并丢弃了部分点击，已自动加大间隔，显示的是实际达到的频率");
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("工作线程:");
+                        ui.label(format!("{}", self.mouse_controller.thread_count()));
                     });
                 }
             });
@@ -364,6 +2132,16 @@ impl eframe::App for AutoClickerApp {
                     ui.label("切换:");
                     ui.code(self.config.toggle_hotkey.to_display_string());
                 });
+                ui.horizontal(|ui| {
+                    ui.label("挂起/恢复:");
+                    ui.code(self.config.suspend_hotkey.to_display_string());
+                    if self.is_hotkey_suspended() {
+                        ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "已挂起");
+                    } else if self.hotkey_manager.as_ref().is_some_and(|manager| !manager.suspend_hotkey_active()) {
+                        ui.colored_label(egui::Color32::GRAY, "未启用")
+                            .on_hover_text("挂起热键被占用或配置无效，详见日志");
+                    }
+                });
 
                 if let Some(ref error) = self.hotkey_error {
                     ui.colored_label(egui::Color32::RED, format!("警告: {}", error));
@@ -376,6 +2154,48 @@ impl eframe::App for AutoClickerApp {
             self.show_hotkey_settings_window(ctx);
         }
 
+        // 宏编辑窗口
+        if self.show_macro_editor {
+            self.show_macro_editor_window(ctx);
+        }
+
+        // 预览按钮被搜索隐藏等情况下，松开鼠标也要停止预览
+        if !ctx.input(|i| i.pointer.any_down())
+            && let Some(mut preview) = self.speed_preview.take()
+        {
+            preview.stop_clicking();
+        }
+
+        // 日志窗口
+        if self.show_log_viewer {
+            self.show_log_viewer_window(ctx);
+        }
+
+        // 托盘退出确认
+        if self.show_quit_confirm {
+            self.show_quit_confirm_window(ctx);
+        }
+
+        // 恢复默认设置确认
+        if self.show_reset_confirm {
+            self.show_reset_confirm_window(ctx);
+        }
+
+        // 新版本说明，引导结束后再显示
+        if !self.whats_new.is_empty() && self.wizard_step.is_none() {
+            self.show_whats_new_window(ctx);
+        }
+
+        // 性能测试窗口
+        if self.show_benchmark {
+            self.show_benchmark_window(ctx);
+        }
+
+        // 首次运行引导
+        if self.wizard_step.is_some() {
+            self.show_first_run_wizard(ctx);
+        }
+
         // 自动最小化到托盘
         if self.config.auto_minimize && self.config.is_running {
             ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
@@ -384,29 +2204,204 @@ impl eframe::App for AutoClickerApp {
 
         // 智能重绘：平衡性能和响应性
         if self.config.is_running {
-            // 运行时：限制统计更新频率
-            if now.duration_since(self.last_stats_update) >= std::time::Duration::from_millis(500) {
-                self.last_stats_update = now;
-                ctx.request_repaint();
-            } else {
-                ctx.request_repaint_after(std::time::Duration::from_millis(100));
-            }
-        } else if self.show_hotkey_settings {
-            // 热键设置窗口打开时保持响应
+            // 运行时：在下次统计采样时重绘，同时至少每 100ms 检查一次热键
+            let until_stats = stats_refresh.saturating_sub(now.duration_since(self.last_stats_update));
+            ctx.request_repaint_after(until_stats.min(IDLE_REPAINT));
+        } else if self.show_hotkey_settings
+            || self.capture_deadline.is_some()
+            || self.countdown.is_some()
+            || self.benchmark_rx.is_some()
+            || self.speed_preview.is_some()
+        {
+            // 热键设置窗口打开、拾取坐标、倒计时、性能测试或预览时保持响应
             ctx.request_repaint_after(std::time::Duration::from_millis(50));
+        } else if self.config.power_saving && !self.config.window_visible && !self.config.hold_mode {
+            // 省电模式：窗口隐藏时进一步降低唤醒频率，热键事件会在队列中等到下次检查。
+            // 长按模式依赖轮询按键状态，降频会漏掉短按，因此不降频
+            ctx.request_repaint_after(POWER_SAVING_REPAINT);
         } else {
             // 空闲时：保持较低频率以检测热键
-            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            ctx.request_repaint_after(IDLE_REPAINT);
         }
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.countdown = None;
         self.stop_clicking();
         let _ = self.config.save();
     }
 }
 
 impl AutoClickerApp {
+    /// 热键预设：选择后填入切换热键，也可把当前热键保存为预设
+    fn hotkey_preset_ui(&mut self, ui: &mut egui::Ui) {
+        let mut presets_changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("预设:");
+            egui::ComboBox::from_id_source("hotkey_preset")
+                .selected_text(if self.hotkey_preset_name.is_empty() {
+                    "选择预设"
+                } else {
+                    self.hotkey_preset_name.as_str()
+                })
+                .show_ui(ui, |ui| {
+                    for preset in &self.hotkey_presets {
+                        let label = format!("{} ({})", preset.name, preset.hotkey.to_display_string());
+                        if ui.selectable_label(self.hotkey_preset_name == preset.name, label).clicked() {
+                            self.hotkey_preset_name = preset.name.clone();
+                            self.temp_toggle_hotkey = preset.hotkey.clone();
+                        }
+                    }
+                });
+
+            let exists = self.hotkey_presets.iter().any(|p| p.name == self.hotkey_preset_name);
+            if ui.add_enabled(exists, egui::Button::new("删除").small()).clicked() {
+                self.hotkey_presets.retain(|p| p.name != self.hotkey_preset_name);
+                self.hotkey_preset_name.clear();
+                presets_changed = true;
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.hotkey_preset_name)
+                .hint_text("预设名称")
+                .desired_width(120.0));
+            let name = self.hotkey_preset_name.trim().to_string();
+            if ui.add_enabled(!name.is_empty(), egui::Button::new("保存为预设").small())
+                .on_hover_text("保存下方设置的切换热键，同名预设会被覆盖")
+                .clicked() {
+                let hotkey = self.temp_toggle_hotkey.clone();
+                match self.hotkey_presets.iter_mut().find(|p| p.name == name) {
+                    Some(preset) => preset.hotkey = hotkey,
+                    None => self.hotkey_presets.push(HotkeyPreset { name, hotkey }),
+                }
+                presets_changed = true;
+            }
+        });
+
+        if presets_changed && let Err(e) = HotkeyPreset::save_all(&self.hotkey_presets) {
+            self.status_message = e;
+        }
+    }
+
+    /// 点击节奏编辑：每格对应一个点击间隔，点亮的格子点击，熄灭的格子跳过
+    fn click_pattern_ui(&mut self, ui: &mut egui::Ui) {
+        const MAX_SLOTS: usize = 32;
+        let mut changed = false;
+        let pattern = &mut self.config.click_pattern;
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label("点击节奏:")
+                .on_hover_text("按点击间隔逐格循环：● 点击，○ 跳过。为空时每格都点击");
+            for slot in pattern.iter_mut() {
+                if ui.small_button(if *slot { "●" } else { "○" }).clicked() {
+                    *slot = !*slot;
+                    changed = true;
+                }
+            }
+            if pattern.len() < MAX_SLOTS && ui.small_button("+").clicked() {
+                pattern.push(true);
+                changed = true;
+            }
+            if !pattern.is_empty() && ui.small_button("-").clicked() {
+                pattern.pop();
+                changed = true;
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let (clicks, skips) = &mut self.pattern_fill;
+            ui.label("点击");
+            ui.add(egui::DragValue::new(clicks).clamp_range(1..=16));
+            ui.label("次，跳过");
+            ui.add(egui::DragValue::new(skips).clamp_range(0..=16));
+            ui.label("次");
+            if ui.small_button("生成").clicked() {
+                *pattern = std::iter::repeat_n(true, *clicks).chain(std::iter::repeat_n(false, *skips)).collect();
+                changed = true;
+            }
+            if ui.small_button("清空").clicked() {
+                pattern.clear();
+                changed = true;
+            }
+        });
+
+        if changed {
+            let _ = self.config.save();
+        }
+    }
+
+    /// 编辑额外热键列表，修改在点击“应用”后生效
+    fn hotkey_bindings_ui(&mut self, ui: &mut egui::Ui) {
+        let mut remove_index = None;
+        let categories = || HOTKEY_KEYS.iter().filter(|(category, _)| !POLLED_KEY_CATEGORIES.contains(category));
+
+        for (i, binding) in self.temp_hotkey_bindings.iter_mut().enumerate() {
+            ui.separator();
+            Self::hotkey_modifier_ui(ui, &mut binding.hotkey.modifiers);
+            ui.horizontal(|ui| {
+                Self::key_combo_ui(ui, ("binding_key", i), &mut binding.hotkey.key, categories());
+
+                egui::ComboBox::from_id_source(("binding_action", i))
+                    .selected_text(binding.action.to_string())
+                    .show_ui(ui, |ui| {
+                        let first_profile = self.profiles.first().cloned().unwrap_or_default();
+                        for option in [
+                            BoundAction::Start,
+                            BoundAction::Stop,
+                            BoundAction::Toggle,
+                            BoundAction::StartAtCps(self.config.cps_value),
+                            BoundAction::StartProfile(first_profile),
+                        ] {
+                            let same_kind = std::mem::discriminant(&option) == std::mem::discriminant(&binding.action);
+                            let label = match &option {
+                                BoundAction::StartAtCps(_) => "指定 CPS 开始".to_string(),
+                                BoundAction::StartProfile(_) => "运行方案".to_string(),
+                                other => other.to_string(),
+                            };
+                            if ui.selectable_label(same_kind, label).clicked() && !same_kind {
+                                binding.action = option;
+                            }
+                        }
+                    });
+
+                match &mut binding.action {
+                    BoundAction::StartAtCps(cps) => {
+                        ui.add(egui::DragValue::new(cps).clamp_range(1..=1000).suffix(" CPS"));
+                    }
+                    BoundAction::StartProfile(name) => {
+                        egui::ComboBox::from_id_source(("binding_profile", i))
+                            .selected_text(if name.is_empty() { "选择方案" } else { name.as_str() })
+                            .show_ui(ui, |ui| {
+                                for profile in &self.profiles {
+                                    ui.selectable_value(name, profile.clone(), profile);
+                                }
+                            });
+                    }
+                    _ => {}
+                }
+
+                if ui.small_button("删除").clicked() {
+                    remove_index = Some(i);
+                }
+            });
+        }
+
+        if let Some(i) = remove_index {
+            self.temp_hotkey_bindings.remove(i);
+        }
+        if ui.small_button("添加").clicked() {
+            self.temp_hotkey_bindings.push(HotkeyBinding {
+                hotkey: HotkeyConfig {
+                    modifiers: vec!["Ctrl".to_string(), "Shift".to_string()],
+                    key: "F1".to_string(),
+                },
+                action: BoundAction::Start,
+            });
+        }
+    }
+
     fn show_hotkey_settings_window(&mut self, ctx: &egui::Context) {
         let mut apply_clicked = false;
         let mut cancel_clicked = false;
@@ -424,6 +2419,9 @@ impl AutoClickerApp {
                             ui.strong("切换连点热键");
                             ui.add_space(5.0);
 
+                            self.hotkey_preset_ui(ui);
+                            ui.add_space(5.0);
+
                             ui.label("修饰键 (可多选):");
                             Self::hotkey_modifier_ui(ui, &mut self.temp_toggle_hotkey.modifiers);
 
@@ -441,6 +2439,61 @@ impl AutoClickerApp {
                         });
                     });
 
+                    ui.add_space(10.0);
+
+                    // 挂起/恢复热键设置
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.strong("挂起/恢复热键");
+                            ui.label("挂起后除本热键外的所有热键都不再响应");
+                            ui.add_space(5.0);
+
+                            ui.label("修饰键 (可多选):");
+                            Self::hotkey_modifier_ui(ui, &mut self.temp_suspend_hotkey.modifiers);
+
+                            ui.add_space(5.0);
+                            ui.horizontal(|ui| {
+                                ui.label("主按键:");
+                                let categories = HOTKEY_KEYS.iter().filter(|(category, _)| !POLLED_KEY_CATEGORIES.contains(category));
+                                Self::key_combo_ui(ui, "suspend_hotkey_key", &mut self.temp_suspend_hotkey.key, categories);
+                            });
+
+                            ui.add_space(5.0);
+                            ui.horizontal(|ui| {
+                                ui.label("预览:");
+                                ui.code(self.temp_suspend_hotkey.to_display_string());
+                            });
+                        });
+                    });
+
+                    ui.add_space(10.0);
+
+                    // 额外热键
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.strong("额外热键");
+                            ui.label("每个热键执行一个动作，挂起时同样不响应");
+                            self.hotkey_bindings_ui(ui);
+                        });
+                    });
+
+                    ui.add_space(10.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("失效检测:");
+                        if ui.add(egui::DragValue::new(&mut self.config.hotkey_revalidate_secs)
+                            .speed(1.0)
+                            .clamp_range(0..=600)
+                            .suffix(" 秒"))
+                            .on_hover_text("按下热键却收不到系统事件时（如被其他程序抢占），每隔该时间尝试重新注册，0 表示关闭")
+                            .changed() {
+                            if let Some(ref mut hotkey_manager) = self.hotkey_manager {
+                                hotkey_manager.set_revalidate_interval(std::time::Duration::from_secs(self.config.hotkey_revalidate_secs));
+                            }
+                            let _ = self.config.save();
+                        }
+                    });
+
                     ui.add_space(15.0);
 
                     // 按钮区域
@@ -471,6 +2524,8 @@ impl AutoClickerApp {
                         ui.label("• 可以选择多个修饰键组合");
                         ui.label("• 修饰键 + 主按键 = 完整热键");
                         ui.label("• 建议使用不常用的组合避免冲突");
+                        ui.label("• 仅修饰键：快速双击该修饰键切换连点，忽略其他修饰键");
+                        ui.label("• 手柄：使用 XInput 手柄（如 Xbox 手柄）的按键，忽略修饰键");
                     });
                 });
             });
@@ -481,22 +2536,226 @@ impl AutoClickerApp {
 
         if cancel_clicked {
             self.temp_toggle_hotkey = self.config.toggle_hotkey.clone();
+            self.temp_suspend_hotkey = self.config.suspend_hotkey.clone();
+            self.temp_hotkey_bindings = self.config.hotkey_bindings.clone();
             self.show_hotkey_settings = false;
         }
 
         if reset_clicked {
-            self.temp_toggle_hotkey = HotkeyConfig {
-                modifiers: vec![],
-                key: "F1".to_string(),
-            };
+            let defaults = AppConfig::default();
+            self.temp_toggle_hotkey = defaults.toggle_hotkey;
+            self.temp_suspend_hotkey = defaults.suspend_hotkey;
+            self.temp_hotkey_bindings = defaults.hotkey_bindings;
         }
     }
 
-    fn hotkey_modifier_ui(ui: &mut egui::Ui, modifiers: &mut Vec<String>) {
-        let available_modifiers = ["Ctrl", "Alt", "Shift", "Win"];
+    fn show_first_run_wizard(&mut self, ctx: &egui::Context) {
+        const STEP_COUNT: usize = 3;
+        let Some(step) = self.wizard_step else {
+            return;
+        };
+
+        let mut next_step = Some(step);
+        let mut finished = false;
+
+        egui::Window::new("欢迎使用 Rust-AC")
+            .collapsible(false)
+            .resizable(false)
+            .default_width(360.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.label(format!("快速设置 ({}/{})", step + 1, STEP_COUNT));
+                ui.separator();
+
+                match step {
+                    0 => {
+                        ui.strong("1. 选择切换热键");
+                        ui.label("按下热键即可开始或停止连点");
+                        ui.add_space(5.0);
+                        Self::hotkey_modifier_ui(ui, &mut self.temp_toggle_hotkey.modifiers);
+                        ui.horizontal(|ui| {
+                            ui.label("主按键:");
+                            Self::hotkey_key_ui(ui, &mut self.temp_toggle_hotkey.key);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("预览:");
+                            ui.code(self.temp_toggle_hotkey.to_display_string());
+                        });
+                    }
+                    1 => {
+                        ui.strong("2. 选择点击速度");
+                        ui.add_space(5.0);
+                        ui.horizontal(|ui| {
+                            ui.label("点击频率:");
+                            ui.add(egui::DragValue::new(&mut self.wizard_cps_value)
+                                .speed(1.0)
+                                .clamp_range(1..=1000)
+                                .suffix(" CPS"));
+                        });
+                        ui.horizontal(|ui| {
+                            for (label, cps) in [("慢速", 2), ("中等", 10), ("快速", 20)] {
+                                if ui.small_button(label).clicked() {
+                                    self.wizard_cps_value = cps;
+                                }
+                            }
+                        });
+                    }
+                    _ => {
+                        ui.strong("3. 选择触发模式");
+                        ui.add_space(5.0);
+                        ui.radio_value(&mut self.wizard_hold_mode, false, "切换：按一次开始，再按一次停止");
+                        ui.radio_value(&mut self.wizard_hold_mode, true, "长按：按住热键连点，松开停止");
+                    }
+                }
+
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if step > 0 && ui.button("上一步").clicked() {
+                        next_step = Some(step - 1);
+                    }
+                    if step + 1 < STEP_COUNT {
+                        if ui.button("下一步").clicked() {
+                            next_step = Some(step + 1);
+                        }
+                    } else if ui.button("完成").clicked() {
+                        finished = true;
+                    }
+                    if ui.button("跳过").on_hover_text("使用默认设置，以后不再显示").clicked() {
+                        next_step = None;
+                    }
+                });
+            });
+
+        if finished {
+            // 速度以 CPS 模式保存，并应用所选热键
+            self.config.cps_value = self.wizard_cps_value;
+            self.config.hold_mode = self.wizard_hold_mode;
+            self.config.interval_mode = IntervalMode::CPS;
+            self.cps_input = self.config.cps_value.to_string();
+            self.apply_hotkey_settings();
+            next_step = None;
+        }
+
+        if next_step.is_none() {
+            self.temp_toggle_hotkey = self.config.toggle_hotkey.clone();
+            self.config.first_run_complete = true;
+            let _ = self.config.save();
+        }
+        self.wizard_step = next_step;
+    }
+
+    fn show_macro_editor_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut changed = false;
+        let mut remove_index = None;
+        let mut move_up_index = None;
+
+        egui::Window::new("宏编辑")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.label("步骤按顺序循环执行:");
+                ui.add_space(5.0);
+
+                egui::ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                    for (i, step) in self.config.macro_steps.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{}.", i + 1));
+                            match step {
+                                ActionStep::KeyPress { key } => {
+                                    ui.label("按键");
+                                    // 宏按键不支持仅修饰键和手柄按键
+                                    let categories = HOTKEY_KEYS.iter().filter(|(category, _)| !POLLED_KEY_CATEGORIES.contains(category));
+                                    changed |= Self::key_combo_ui(ui, ("macro_key", i), key, categories);
+                                }
+                                ActionStep::MouseClick { button } => {
+                                    ui.label("点击");
+                                    changed |= ui.radio_value(button, MouseButton::Left, "左键").changed();
+                                    changed |= ui.radio_value(button, MouseButton::Right, "右键").changed();
+                                }
+                                ActionStep::Wait { ms } => {
+                                    ui.label("等待");
+                                    changed |= ui.add(egui::DragValue::new(ms)
+                                        .speed(10.0)
+                                        .clamp_range(0..=60000)
+                                        .suffix(" ms")).changed();
+                                }
+                                ActionStep::MoveTo { x, y } => {
+                                    ui.label("移动到");
+                                    changed |= ui.add(egui::DragValue::new(x).prefix("X: ")).changed();
+                                    changed |= ui.add(egui::DragValue::new(y).prefix("Y: ")).changed();
+                                }
+                                ActionStep::Scroll { direction, amount } => {
+                                    ui.label("滚动");
+                                    changed |= ui.radio_value(direction, ScrollDirection::Vertical, "垂直").changed();
+                                    changed |= ui.radio_value(direction, ScrollDirection::Horizontal, "水平").changed();
+                                    changed |= ui.add(egui::DragValue::new(amount)
+                                        .clamp_range(-50..=50)
+                                        .suffix(" 格"))
+                                        .on_hover_text("正数向上/向右，负数向下/向左")
+                                        .changed();
+                                }
+                            }
+
+                            if i > 0 && ui.small_button("上移").clicked() {
+                                move_up_index = Some(i);
+                            }
+                            if ui.small_button("删除").clicked() {
+                                remove_index = Some(i);
+                            }
+                        });
+                    }
+                });
+
+                ui.separator();
 
+                ui.horizontal(|ui| {
+                    ui.label("添加:");
+                    let new_step = if ui.small_button("按键").clicked() {
+                        Some(ActionStep::KeyPress { key: "A".to_string() })
+                    } else if ui.small_button("点击").clicked() {
+                        Some(ActionStep::MouseClick { button: self.config.mouse_button })
+                    } else if ui.small_button("等待").clicked() {
+                        Some(ActionStep::Wait { ms: 100 })
+                    } else if ui.small_button("移动").clicked() {
+                        Some(ActionStep::MoveTo { x: 0, y: 0 })
+                    } else if ui.small_button("滚动").clicked() {
+                        Some(ActionStep::Scroll { direction: ScrollDirection::Vertical, amount: 1 })
+                    } else {
+                        None
+                    };
+                    if let Some(step) = new_step {
+                        self.config.macro_steps.push(step);
+                        changed = true;
+                    }
+                });
+
+                if self.config.is_running && self.config.macro_mode {
+                    ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "修改将在下次开始时生效");
+                }
+            });
+
+        if let Some(i) = move_up_index {
+            self.config.macro_steps.swap(i - 1, i);
+            changed = true;
+        }
+        if let Some(i) = remove_index {
+            self.config.macro_steps.remove(i);
+            changed = true;
+        }
+        if changed {
+            let _ = self.config.save();
+        }
+        if !open {
+            self.show_macro_editor = false;
+        }
+    }
+
+    fn hotkey_modifier_ui(ui: &mut egui::Ui, modifiers: &mut Vec<String>) {
         ui.horizontal(|ui| {
-            for modifier in available_modifiers {
+            for modifier in HOTKEY_MODIFIERS {
                 let mut checked = modifiers.contains(&modifier.to_string());
                 if ui.checkbox(&mut checked, modifier).changed() {
                     if checked {
@@ -512,44 +2771,100 @@ impl AutoClickerApp {
     }
 
     fn hotkey_key_ui(ui: &mut egui::Ui, key: &mut String) {
-        let keys = [
-            ("功能键", vec!["F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12"]),
-            ("字母键", vec!["A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S", "T", "U", "V", "W", "X", "Y", "Z"]),
-            ("导航键", vec!["Home", "End", "PageUp", "PageDown", "Insert", "Delete"]),
-            ("锁定键", vec!["CapsLock", "NumLock", "ScrollLock"]),
-            ("特殊键", vec!["Space", "Enter", "Esc", "Tab"]),
-        ];
-
         // 使用当前key值作为ID的一部分来确保唯一性
         let combo_id = format!("hotkey_key_{}", key);
+        Self::key_combo_ui(ui, combo_id, key, HOTKEY_KEYS.iter());
+    }
+
+    fn key_combo_ui<'a>(
+        ui: &mut egui::Ui,
+        combo_id: impl std::hash::Hash,
+        key: &mut String,
+        categories: impl Iterator<Item = &'a (&'static str, &'static [&'static str])>,
+    ) -> bool {
+        let mut changed = false;
         egui::ComboBox::from_id_source(combo_id)
-            .selected_text(key.as_str())
+            .selected_text(HotkeyConfig::key_label(key))
             .width(120.0)
             .show_ui(ui, |ui| {
-                for (category, key_list) in keys {
+                for (category, key_list) in categories {
                     ui.label(format!("--- {} ---", category));
-                    for k in key_list {
-                        ui.selectable_value(key, k.to_string(), k);
+                    for k in key_list.iter() {
+                        changed |= ui
+                            .selectable_value(key, k.to_string(), HotkeyConfig::key_label(k))
+                            .changed();
                     }
                     ui.separator();
                 }
             });
+        changed
     }
 
     fn apply_hotkey_settings(&mut self) {
+        // 仅修饰键和手柄按键热键不使用额外的修饰键
+        if self.temp_toggle_hotkey.double_tap_modifier().is_some() || self.temp_toggle_hotkey.gamepad_button().is_some() {
+            self.temp_toggle_hotkey.modifiers.clear();
+        }
         self.config.toggle_hotkey = self.temp_toggle_hotkey.clone();
+        self.config.suspend_hotkey = self.temp_suspend_hotkey.clone();
+        self.config.hotkey_bindings = self.temp_hotkey_bindings.clone();
 
         if let Some(ref mut hotkey_manager) = self.hotkey_manager {
-            match hotkey_manager.update_hotkeys(&self.config.toggle_hotkey) {
+            match Self::register_all_hotkeys(hotkey_manager, &self.config) {
                 Ok(_) => {
                     self.hotkey_error = None;
                     self.show_hotkey_settings = false;
                     self.status_message = "热键设置已更新".to_string();
                 }
                 Err(e) => {
+                    log_error!("热键注册失败: {}", e);
                     self.hotkey_error = Some(e);
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_offered_key_maps_to_a_hotkey() {
+        for (category, keys) in HOTKEY_KEYS {
+            for key in keys {
+                let config = HotkeyConfig {
+                    modifiers: vec![],
+                    key: key.to_string(),
+                };
+                // 仅修饰键和手柄按键通过轮询检测，不经过系统注册
+                if config.double_tap_modifier().is_some() || config.gamepad_button().is_some() {
+                    continue;
+                }
+                assert!(
+                    config.to_global_hotkey().is_ok(),
+                    "{} 中的按键 {} 无法注册",
+                    category,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_offered_modifier_maps_to_a_hotkey() {
+        for modifier in HOTKEY_MODIFIERS {
+            let config = HotkeyConfig {
+                modifiers: vec![modifier.to_string()],
+                key: "F1".to_string(),
+            };
+            assert!(config.to_global_hotkey().is_ok(), "修饰键 {} 无法注册", modifier);
+        }
+
+        let all = HotkeyConfig {
+            modifiers: HOTKEY_MODIFIERS.iter().map(|m| m.to_string()).collect(),
+            key: "A".to_string(),
+        };
+        assert!(all.to_global_hotkey().is_ok());
+    }
+}
@@ -0,0 +1,55 @@
+use crate::config::MouseButton;
+use crate::input_backend::DryRunBackend;
+use crate::mouse_controller::{ClickSettings, MouseController};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 性能测试使用的点击间隔（毫秒），尽量逼近本机上限
+const BENCHMARK_INTERVAL_MS: u64 = 1;
+
+/// 性能测试结果
+#[derive(Debug, Clone, Copy)]
+pub struct BenchmarkResult {
+    pub cps: f64,
+    pub clicks: u64,
+    pub mean_interval_ms: f64,
+    pub jitter_ms: f64,
+    pub threads: usize,
+}
+
+/// 以空跑模式全速运行点击引擎，测量可达到的最高频率
+///
+/// 不会产生任何真实点击，调用会阻塞 duration 时长
+pub fn run(duration: Duration) -> BenchmarkResult {
+    let backend = Arc::new(DryRunBackend::new());
+    let mut controller = MouseController::with_backend(backend.clone());
+
+    controller.start_clicking(ClickSettings::new(MouseButton::Left, BENCHMARK_INTERVAL_MS));
+    std::thread::sleep(duration);
+    let cps = controller.get_cps();
+    let clicks = controller.get_click_count();
+    let threads = controller.thread_count();
+    controller.stop_clicking();
+
+    let stats = backend.interval_stats();
+    BenchmarkResult {
+        cps,
+        clicks,
+        mean_interval_ms: stats.mean_ms(),
+        jitter_ms: stats.jitter_ms(),
+        threads,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_clicks_without_injecting() {
+        let result = run(Duration::from_millis(300));
+        assert!(result.clicks > 0);
+        assert!(result.cps > 0.0);
+        assert!(result.mean_interval_ms > 0.0);
+    }
+}
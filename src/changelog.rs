@@ -0,0 +1,85 @@
+use std::cmp::Ordering;
+
+/// 当前程序版本
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 一个版本的更新说明
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Release {
+    pub version: &'static str,
+    pub changes: &'static [&'static str],
+}
+
+/// 内置的更新说明，新版本在前
+pub const RELEASES: &[Release] = &[Release {
+    version: "0.1.5",
+    changes: &[
+        "新增“拟人”预设：一键设置位置偏移、按住时长和间隔浮动",
+        "连点中可以暂停和继续，可选择暂停时是否停止计时",
+        "工具菜单新增“节奏测试”，按三快一慢的节奏点击，方便确认点击生效",
+        "切换热键可以使用 Xbox 等 XInput 手柄的按键",
+        "多个固定坐标支持顺序、倒序和随机点击",
+        "系统丢弃点击事件时自动降速，点击计数不再虚高",
+        "连点中从托盘退出需要确认，避免误点中断长时间运行",
+        "新增额外热键、禁止时段、失去焦点时停止、点击节奏和脚本控制",
+    ],
+}];
+
+/// 把 "1.2.3" 形式的版本号拆成数字，无法解析的部分视为 0
+fn parse_version(version: &str) -> Vec<u64> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+/// 按数字逐段比较版本号，缺少的段视为 0
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a, b) = (parse_version(a), parse_version(b));
+    let len = a.len().max(b.len());
+    let segment = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    (0..len)
+        .map(|i| segment(&a, i).cmp(&segment(&b, i)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// 上次看过的版本之后到当前版本为止的更新说明
+///
+/// 没有记录（从没有此功能的旧版本升级）时只返回当前版本的说明
+pub fn unseen_releases(last_seen: Option<&str>) -> Vec<&'static Release> {
+    RELEASES
+        .iter()
+        .filter(|release| compare_versions(release.version, CURRENT_VERSION).is_le())
+        .filter(|release| match last_seen {
+            Some(seen) => compare_versions(release.version, seen).is_gt(),
+            None => compare_versions(release.version, CURRENT_VERSION).is_eq(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_versions_numerically() {
+        assert_eq!(compare_versions("0.1.10", "0.1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("0.2", "0.2.0"), Ordering::Equal);
+        assert_eq!(compare_versions("v1.0.0", "0.9.9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn only_unseen_releases_are_shown() {
+        assert!(unseen_releases(Some(CURRENT_VERSION)).is_empty());
+        assert!(unseen_releases(Some("99.0.0")).is_empty());
+
+        let upgraded = unseen_releases(Some("0.0.1"));
+        assert!(upgraded.iter().any(|release| release.version == CURRENT_VERSION));
+
+        let legacy = unseen_releases(None);
+        assert!(legacy.iter().all(|release| release.version == CURRENT_VERSION));
+    }
+}
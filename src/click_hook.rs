@@ -0,0 +1,73 @@
+use crate::config::ClickPoint;
+use std::sync::Mutex;
+use windows::Win32::Foundation::{LPARAM, LRESULT, WPARAM};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::Threading::GetCurrentProcessId;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CallNextHookEx, GetWindowThreadProcessId, SetWindowsHookExW, UnhookWindowsHookEx,
+    WindowFromPoint, HC_ACTION, HHOOK, LLMHF_INJECTED, MSLLHOOKSTRUCT, WH_MOUSE_LL,
+    WM_LBUTTONDOWN, WM_RBUTTONDOWN,
+};
+
+/// 用户最近一次真实点击的屏幕坐标
+static LAST_CLICK: Mutex<Option<ClickPoint>> = Mutex::new(None);
+
+/// 记录用户真实点击位置的低级鼠标钩子
+///
+/// 钩子回调运行在安装它的线程上，该线程必须持续处理消息（界面线程满足）。
+/// 注入的点击和落在本程序窗口上的点击不会被记录，析构时自动卸载钩子。
+pub struct ClickHook {
+    hook: HHOOK,
+}
+
+impl ClickHook {
+    /// 在当前线程安装钩子
+    pub fn install() -> Result<Self, String> {
+        let instance = unsafe { GetModuleHandleW(None) }
+            .map_err(|e| format!("获取模块句柄失败: {}", e))?;
+        let hook = unsafe { SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook_proc), instance, 0) }
+            .map_err(|e| format!("安装鼠标钩子失败: {}", e))?;
+        Ok(Self { hook })
+    }
+}
+
+impl Drop for ClickHook {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = UnhookWindowsHookEx(self.hook);
+        }
+    }
+}
+
+/// 最近一次真实点击的位置，尚未点击过时返回 None
+pub fn last_click() -> Option<ClickPoint> {
+    LAST_CLICK.lock().ok().and_then(|last| *last)
+}
+
+unsafe extern "system" fn mouse_hook_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HC_ACTION as i32 {
+        let message = wparam.0 as u32;
+        if message == WM_LBUTTONDOWN || message == WM_RBUTTONDOWN {
+            let info = unsafe { &*(lparam.0 as *const MSLLHOOKSTRUCT) };
+            if info.flags & LLMHF_INJECTED == 0 && !is_own_window(info.pt.x, info.pt.y)
+                && let Ok(mut last) = LAST_CLICK.lock()
+            {
+                *last = Some(ClickPoint { x: info.pt.x, y: info.pt.y });
+            }
+        }
+    }
+    unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
+
+/// 该点是否落在本程序自己的窗口上（例如点击界面上的按钮）
+fn is_own_window(x: i32, y: i32) -> bool {
+    unsafe {
+        let hwnd = WindowFromPoint(windows::Win32::Foundation::POINT { x, y });
+        if hwnd.0 == 0 {
+            return false;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        pid == GetCurrentProcessId()
+    }
+}
@@ -0,0 +1,119 @@
+use crate::config::AppConfig;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// 检查命令文件的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 同一命令在该时间内重复出现时只执行一次
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 通过命令文件下达的指令
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileCommand {
+    Start,
+    Stop,
+    Toggle,
+}
+
+impl FileCommand {
+    /// 解析单个单词的命令，忽略大小写和首尾空白，无法识别时返回 None
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "start" => Some(FileCommand::Start),
+            "stop" => Some(FileCommand::Stop),
+            "toggle" => Some(FileCommand::Toggle),
+            _ => None,
+        }
+    }
+}
+
+/// 命令文件的默认位置：配置目录下的 `command`
+pub fn command_path() -> Result<PathBuf, String> {
+    Ok(AppConfig::get_config_dir()?.join("command"))
+}
+
+/// 轮询命令文件，读到命令后清空文件
+///
+/// 脚本只需 `echo start > %USERPROFILE%\.config\Rust-ac\command` 即可控制程序
+pub struct CommandFile {
+    path: PathBuf,
+    last_poll: Option<Instant>,
+    last_command: Option<(FileCommand, Instant)>,
+}
+
+impl CommandFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            last_poll: None,
+            last_command: None,
+        }
+    }
+
+    /// 按间隔检查命令文件，返回需要执行的命令
+    ///
+    /// 文件为空或不存在时只读取元数据；未知命令和防抖时间内的重复命令会被忽略
+    pub fn poll(&mut self) -> Option<FileCommand> {
+        let now = Instant::now();
+        if self.last_poll.is_some_and(|last| now.duration_since(last) < POLL_INTERVAL) {
+            return None;
+        }
+        self.last_poll = Some(now);
+
+        if fs::metadata(&self.path).map_or(true, |meta| meta.len() == 0) {
+            return None;
+        }
+        let content = fs::read_to_string(&self.path).ok()?;
+        if let Err(e) = fs::write(&self.path, "") {
+            crate::log_warn!("清空命令文件失败: {}", e);
+        }
+
+        let text = content.trim();
+        let Some(command) = FileCommand::parse(text) else {
+            crate::log_warn!("忽略无法识别的命令: {}", text);
+            return None;
+        };
+        if let Some((last, at)) = self.last_command
+            && last == command
+            && now.duration_since(at) < DEBOUNCE
+        {
+            return None;
+        }
+        self.last_command = Some((command, now));
+        Some(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_clears_and_debounces_commands() {
+        let path = std::env::temp_dir().join(format!("rust-ac-command-{}", std::process::id()));
+        let mut commands = CommandFile::new(path.clone());
+        assert_eq!(commands.poll(), None);
+
+        fs::write(&path, " Start\r\n").unwrap();
+        commands.last_poll = None;
+        assert_eq!(commands.poll(), Some(FileCommand::Start));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+        // 防抖时间内的重复命令被忽略，不同命令照常执行
+        fs::write(&path, "start").unwrap();
+        commands.last_poll = None;
+        assert_eq!(commands.poll(), None);
+        fs::write(&path, "stop").unwrap();
+        commands.last_poll = None;
+        assert_eq!(commands.poll(), Some(FileCommand::Stop));
+
+        fs::write(&path, "rm -rf").unwrap();
+        commands.last_poll = None;
+        assert_eq!(commands.poll(), None);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+        let _ = fs::remove_file(&path);
+    }
+}
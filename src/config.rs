@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use global_hotkey::hotkey::{Code, Modifiers};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::fs;
 
@@ -24,12 +25,131 @@ impl std::fmt::Display for MouseButton {
     }
 }
 
+/// 滚轮方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScrollDirection {
+    /// 普通滚轮，正数向上
+    #[default]
+    Vertical,
+    /// 倾斜滚轮（水平滚动），正数向右
+    Horizontal,
+}
+
+impl std::fmt::Display for ScrollDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScrollDirection::Vertical => write!(f, "垂直"),
+            ScrollDirection::Horizontal => write!(f, "水平"),
+        }
+    }
+}
+
+/// 多个固定坐标的轮流顺序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PointOrder {
+    /// 按添加顺序
+    #[default]
+    Sequential,
+    /// 从最后一个坐标开始倒序
+    Reverse,
+    /// 每一轮随机打乱顺序，每个坐标仍各点一次
+    Random,
+}
+
+impl std::fmt::Display for PointOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PointOrder::Sequential => write!(f, "顺序"),
+            PointOrder::Reverse => write!(f, "倒序"),
+            PointOrder::Random => write!(f, "随机"),
+        }
+    }
+}
+
+/// 按物理位置识别的按键：(名称, 键码, 扫描码, 美式布局下的虚拟键码)
+///
+/// 名称以美式键盘上该位置的字符命名，热键按键位而不是字符匹配，
+/// 例如法语 AZERTY 键盘上的 "Q" 指的是印有 A 的那个键
+const POSITION_KEYS: [(&str, Code, u16, u16); 37] = [
+    ("A", Code::KeyA, 0x1E, 0x41),
+    ("B", Code::KeyB, 0x30, 0x42),
+    ("C", Code::KeyC, 0x2E, 0x43),
+    ("D", Code::KeyD, 0x20, 0x44),
+    ("E", Code::KeyE, 0x12, 0x45),
+    ("F", Code::KeyF, 0x21, 0x46),
+    ("G", Code::KeyG, 0x22, 0x47),
+    ("H", Code::KeyH, 0x23, 0x48),
+    ("I", Code::KeyI, 0x17, 0x49),
+    ("J", Code::KeyJ, 0x24, 0x4A),
+    ("K", Code::KeyK, 0x25, 0x4B),
+    ("L", Code::KeyL, 0x26, 0x4C),
+    ("M", Code::KeyM, 0x32, 0x4D),
+    ("N", Code::KeyN, 0x31, 0x4E),
+    ("O", Code::KeyO, 0x18, 0x4F),
+    ("P", Code::KeyP, 0x19, 0x50),
+    ("Q", Code::KeyQ, 0x10, 0x51),
+    ("R", Code::KeyR, 0x13, 0x52),
+    ("S", Code::KeyS, 0x1F, 0x53),
+    ("T", Code::KeyT, 0x14, 0x54),
+    ("U", Code::KeyU, 0x16, 0x55),
+    ("V", Code::KeyV, 0x2F, 0x56),
+    ("W", Code::KeyW, 0x11, 0x57),
+    ("X", Code::KeyX, 0x2D, 0x58),
+    ("Y", Code::KeyY, 0x15, 0x59),
+    ("Z", Code::KeyZ, 0x2C, 0x5A),
+    ("Minus", Code::Minus, 0x0C, 0xBD),
+    ("Equal", Code::Equal, 0x0D, 0xBB),
+    ("BracketLeft", Code::BracketLeft, 0x1A, 0xDB),
+    ("BracketRight", Code::BracketRight, 0x1B, 0xDD),
+    ("Semicolon", Code::Semicolon, 0x27, 0xBA),
+    ("Quote", Code::Quote, 0x28, 0xDE),
+    ("Backquote", Code::Backquote, 0x29, 0xC0),
+    ("Backslash", Code::Backslash, 0x2B, 0xDC),
+    ("Comma", Code::Comma, 0x33, 0xBC),
+    ("Period", Code::Period, 0x34, 0xBE),
+    ("Slash", Code::Slash, 0x35, 0xBF),
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HotkeyConfig {
     pub modifiers: Vec<String>,
     pub key: String,
 }
 
+/// 额外热键触发的动作
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundAction {
+    /// 开始连点，已在运行时忽略
+    Start,
+    /// 停止连点
+    Stop,
+    /// 开始或停止连点，与切换热键相同
+    Toggle,
+    /// 改为指定 CPS 后开始连点
+    StartAtCps(u64),
+    /// 切换到配置方案后开始，可用于运行方案中保存的宏
+    StartProfile(String),
+}
+
+impl std::fmt::Display for BoundAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoundAction::Start => write!(f, "开始"),
+            BoundAction::Stop => write!(f, "停止"),
+            BoundAction::Toggle => write!(f, "切换"),
+            BoundAction::StartAtCps(cps) => write!(f, "以 {} CPS 开始", cps),
+            BoundAction::StartProfile(name) => write!(f, "运行方案「{}」", name),
+        }
+    }
+}
+
+/// 绑定到额外热键上的动作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyBinding {
+    pub hotkey: HotkeyConfig,
+    pub action: BoundAction,
+}
+
 impl Default for HotkeyConfig {
     fn default() -> Self {
         Self {
@@ -40,14 +160,97 @@ impl Default for HotkeyConfig {
 }
 
 impl HotkeyConfig {
+    /// 仅修饰键热键（双击修饰键触发）可选的主按键
+    pub const DOUBLE_TAP_KEYS: [&'static str; 3] = ["DoubleCtrl", "DoubleShift", "DoubleAlt"];
+
+    /// 手柄（XInput）按键热键可选的主按键，通过轮询检测，忽略修饰键
+    pub const GAMEPAD_KEYS: [&'static str; 14] = [
+        "PadA", "PadB", "PadX", "PadY", "PadLB", "PadRB", "PadLS", "PadRS",
+        "PadStart", "PadBack", "PadUp", "PadDown", "PadLeft", "PadRight",
+    ];
+
+    /// 若为手柄按键热键，返回按键名
+    pub fn gamepad_button(&self) -> Option<&str> {
+        Self::GAMEPAD_KEYS.contains(&self.key.as_str()).then_some(self.key.as_str())
+    }
+
+    /// 若为双击修饰键热键，返回对应的修饰键名
+    pub fn double_tap_modifier(&self) -> Option<&str> {
+        self.key.strip_prefix("Double")
+            .filter(|m| matches!(*m, "Ctrl" | "Shift" | "Alt"))
+    }
+
+    /// 主按键在界面上的显示名称
+    pub fn key_label(key: &str) -> String {
+        let us_symbol = match key {
+            "Minus" => "-",
+            "Equal" => "=",
+            "BracketLeft" => "[",
+            "BracketRight" => "]",
+            "Semicolon" => ";",
+            "Quote" => "'",
+            "Backquote" => "`",
+            "Backslash" => "\\",
+            "Comma" => ",",
+            "Period" => ".",
+            "Slash" => "/",
+            _ => "",
+        };
+        if Self::GAMEPAD_KEYS.contains(&key) {
+            return format!("手柄 {}", &key["Pad".len()..]);
+        }
+        match key.strip_prefix("Double") {
+            Some(modifier) if !modifier.is_empty() => format!("双击{} (仅修饰键)", modifier),
+            _ if !us_symbol.is_empty() => format!("{} (美式 {} 键位)", key, us_symbol),
+            _ => key.to_string(),
+        }
+    }
+
     pub fn to_display_string(&self) -> String {
-        if self.modifiers.is_empty() {
+        if let Some(modifier) = self.double_tap_modifier() {
+            format!("双击{}", modifier)
+        } else if self.gamepad_button().is_some() {
+            Self::key_label(&self.key)
+        } else if self.modifiers.is_empty() {
             self.key.clone()
         } else {
             format!("{}+{}", self.modifiers.join("+"), self.key)
         }
     }
 
+    /// 按位置识别的按键对应的扫描码，其他按键返回 None
+    pub fn scan_code(key: &str) -> Option<u16> {
+        POSITION_KEYS
+            .iter()
+            .find(|(name, ..)| *name == key)
+            .map(|&(_, _, scan, _)| scan)
+    }
+
+    /// 虚拟键码对应的 global_hotkey 键码（只包括按位置识别的按键）
+    fn code_for_vk(vk: u16) -> Option<Code> {
+        POSITION_KEYS
+            .iter()
+            .find(|&&(_, _, _, us_vk)| us_vk == vk)
+            .map(|&(_, code, ..)| code)
+    }
+
+    /// 按当前键盘布局转换为可注册的组合，保证按下的是同一个物理按键
+    ///
+    /// global_hotkey 按虚拟键码注册，而虚拟键码随布局变化。`vk_for_scan`
+    /// 返回扫描码在当前布局下的虚拟键码，再反查出注册时应使用的键码
+    pub fn to_layout_hotkey(
+        &self,
+        vk_for_scan: impl Fn(u16) -> Option<u16>,
+    ) -> Result<(Modifiers, Code), String> {
+        let (modifiers, code) = self.to_global_hotkey()?;
+        let layout_code = Self::scan_code(&self.key)
+            .and_then(vk_for_scan)
+            .and_then(Self::code_for_vk)
+            .unwrap_or(code);
+        Ok((modifiers, layout_code))
+    }
+
+    /// 转换为 global_hotkey 可注册的组合，双击修饰键热键无法注册，会返回错误
     pub fn to_global_hotkey(&self) -> Result<(Modifiers, Code), String> {
         let mut modifiers = Modifiers::empty();
 
@@ -87,39 +290,91 @@ impl HotkeyConfig {
             "CapsLock" => Code::CapsLock,
             "NumLock" => Code::NumLock,
             "ScrollLock" => Code::ScrollLock,
-            "A" => Code::KeyA,
-            "B" => Code::KeyB,
-            "C" => Code::KeyC,
-            "D" => Code::KeyD,
-            "E" => Code::KeyE,
-            "F" => Code::KeyF,
-            "G" => Code::KeyG,
-            "H" => Code::KeyH,
-            "I" => Code::KeyI,
-            "J" => Code::KeyJ,
-            "K" => Code::KeyK,
-            "L" => Code::KeyL,
-            "M" => Code::KeyM,
-            "N" => Code::KeyN,
-            "O" => Code::KeyO,
-            "P" => Code::KeyP,
-            "Q" => Code::KeyQ,
-            "R" => Code::KeyR,
-            "S" => Code::KeyS,
-            "T" => Code::KeyT,
-            "U" => Code::KeyU,
-            "V" => Code::KeyV,
-            "W" => Code::KeyW,
-            "X" => Code::KeyX,
-            "Y" => Code::KeyY,
-            "Z" => Code::KeyZ,
-            _ => return Err(format!("未知按键: {}", self.key)),
+            key => match POSITION_KEYS.iter().find(|(name, ..)| *name == key) {
+                Some(&(_, code, ..)) => code,
+                None => return Err(format!("未知按键: {}（字母和符号键请使用美式键盘上的位置名称）", key)),
+            },
         };
 
         Ok((modifiers, code))
     }
 }
 
+/// 开始连点的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RunMode {
+    /// 真实点击
+    #[default]
+    Real,
+    /// 空跑，只统计不注入
+    DryRun,
+    /// 只点击一次
+    SingleShot,
+    /// 按“三快一慢”的固定节奏点击几组后停止，用于确认点击确实生效
+    RhythmTest,
+}
+
+impl std::fmt::Display for RunMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RunMode::Real => write!(f, "真实点击"),
+            RunMode::DryRun => write!(f, "空跑测试"),
+            RunMode::SingleShot => write!(f, "单次点击"),
+            RunMode::RhythmTest => write!(f, "节奏测试"),
+        }
+    }
+}
+
+/// 达到点击次数上限后的动作
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LimitAction {
+    /// 停止连点
+    #[default]
+    Stop,
+    /// 计数归零后继续
+    Restart,
+    /// 切换到指定配置方案并继续连点
+    SwitchProfile(String),
+}
+
+/// 命名的热键预设，只包含热键，比完整配置方案更轻量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyPreset {
+    pub name: String,
+    pub hotkey: HotkeyConfig,
+}
+
+impl HotkeyPreset {
+    fn get_presets_path() -> Result<PathBuf, String> {
+        Ok(AppConfig::get_config_dir()?.join("hotkey_presets.json"))
+    }
+
+    /// 读取所有热键预设，文件不存在或损坏时返回空列表
+    pub fn load_all() -> Vec<HotkeyPreset> {
+        let Ok(path) = Self::get_presets_path() else {
+            return Vec::new();
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                crate::log_warn!("解析热键预设失败: {}", e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// 保存全部热键预设
+    pub fn save_all(presets: &[HotkeyPreset]) -> Result<(), String> {
+        let path = Self::get_presets_path()?;
+
+        let json = serde_json::to_string_pretty(presets)
+            .map_err(|e| format!("序列化热键预设失败: {}", e))?;
+
+        fs::write(&path, json)
+            .map_err(|e| format!("写入热键预设失败: {}", e))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum IntervalMode {
     Milliseconds,
@@ -132,18 +387,207 @@ impl Default for IntervalMode {
     }
 }
 
+/// 屏幕坐标点
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ClickPoint {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl ClickPoint {
+    /// 对齐到最近的网格点，grid 不大于 1 时原样返回
+    pub fn snapped(self, grid: u32) -> Self {
+        if grid <= 1 {
+            return self;
+        }
+        let grid = grid as i32;
+        let snap = |v: i32| (v + grid / 2).div_euclid(grid) * grid;
+        Self { x: snap(self.x), y: snap(self.y) }
+    }
+}
+
+/// 一天中的时刻（本地时间，精确到分钟）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct TimeOfDay {
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl TimeOfDay {
+    pub fn new(hour: u8, minute: u8) -> Self {
+        Self { hour, minute }
+    }
+
+    /// 当前本地时刻
+    pub fn now() -> Self {
+        let time = unsafe { windows::Win32::System::SystemInformation::GetLocalTime() };
+        Self::new(time.wHour as u8, time.wMinute as u8)
+    }
+
+    fn minutes(self) -> u32 {
+        self.hour as u32 * 60 + self.minute as u32
+    }
+}
+
+impl std::fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+/// 禁止连点的时间段，包含开始时刻、不含结束时刻
+///
+/// 结束早于开始时表示跨越午夜，例如 23:00-07:00；两者相同时为空时段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: TimeOfDay,
+    pub end: TimeOfDay,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            start: TimeOfDay::new(23, 0),
+            end: TimeOfDay::new(7, 0),
+        }
+    }
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: TimeOfDay) -> bool {
+        let (start, end, time) = (self.start.minutes(), self.end.minutes(), time.minutes());
+        if start <= end {
+            start <= time && time < end
+        } else {
+            time >= start || time < end
+        }
+    }
+}
+
+impl std::fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// 宏中的单个步骤
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ActionStep {
+    KeyPress { key: String },
+    MouseClick { button: MouseButton },
+    Wait { ms: u64 },
+    MoveTo { x: i32, y: i32 },
+    /// 滚动滚轮，amount 为格数，每格计为一次事件
+    Scroll { direction: ScrollDirection, amount: i32 },
+}
+
+impl std::fmt::Display for ActionStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActionStep::KeyPress { key } => write!(f, "按键 {}", key),
+            ActionStep::MouseClick { button } => write!(f, "点击{}", button),
+            ActionStep::Wait { ms } => write!(f, "等待 {}ms", ms),
+            ActionStep::MoveTo { x, y } => write!(f, "移动到 ({}, {})", x, y),
+            ActionStep::Scroll { direction, amount } => write!(f, "{}滚动 {:+} 格", direction, amount),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     pub mouse_button: MouseButton,
     pub click_interval: u64,
     pub cps_value: u64,
     pub interval_mode: IntervalMode,
     pub is_running: bool,
+    /// 窗口当前是否可见，只在运行期间使用，不写入配置
+    ///
+    /// 程序总是以可见窗口启动，避免托盘不可用时无法找回窗口
+    #[serde(skip, default = "default_true")]
     pub window_visible: bool,
     pub toggle_hotkey: HotkeyConfig,
+    pub suspend_hotkey: HotkeyConfig, // 挂起/恢复全部热键处理
+    /// 热键事件丢失时尝试重新注册的检测间隔（秒），0 表示不检测
+    pub hotkey_revalidate_secs: u64,
+    /// 额外热键及其动作
+    pub hotkey_bindings: Vec<HotkeyBinding>,
     pub total_clicks: u64,
+    /// 上次手动重置以来的累计点击，跨会话保留
+    pub clicks_since_reset: u64,
     pub auto_minimize: bool,
+    /// 窗口隐藏且未在连点时降低重绘频率，热键响应会变慢
+    pub power_saving: bool,
+    /// 运行中统计数据的刷新间隔（毫秒），界面限制在 100..=2000
+    pub stats_refresh_ms: u64,
+    /// 暂停期间停止计时，运行时间和 CPS 不包含暂停的时长
+    #[serde(default = "default_true")]
+    pub pause_freezes_stats: bool,
+    /// 监视配置目录下的 command 文件，执行其中的 start/stop/toggle 命令
+    pub command_file_enabled: bool,
     pub hold_mode: bool, // true: 长按触发, false: 切换模式
+    pub macro_mode: bool, // true: 开始时循环执行宏，而不是单纯连点
+    pub macro_steps: Vec<ActionStep>,
+    pub fixed_position: bool, // true: 在固定坐标点击, false: 在当前光标位置点击
+    pub click_points: Vec<ClickPoint>,
+    pub point_order: PointOrder,
+    /// 在固定坐标之间沿曲线平滑移动光标，而不是直接跳过去
+    pub glide_between_points: bool,
+    /// 平滑移动的速度（像素/秒）
+    pub glide_speed: u32,
+    /// 在当前光标位置点击时附带绝对移动事件重新确认位置
+    pub reassert_position: bool,
+    /// 拾取坐标时对齐的网格大小（像素），0 表示不对齐
+    pub capture_grid: u32,
+    /// 设置后固定坐标为相对该标题窗口客户区的坐标
+    pub target_window: Option<String>,
+    pub monitor_profiles: HashMap<String, Vec<ClickPoint>>, // 显示器布局签名 -> 坐标
+    pub suppress_cps_warning: bool,
+    pub cps_warning_threshold: u64,
+    pub active_profile: Option<String>,
+    /// true: 开始时循环执行拖拽（按下、沿路径移动、松开）
+    pub drag_mode: bool,
+    pub drag_path: Vec<ClickPoint>,
+    /// 拖拽移动速度（像素/秒）
+    pub drag_speed: u32,
+    /// 点击次数上限，0 表示不限制
+    pub click_limit: u64,
+    pub on_limit: LimitAction,
+    /// 点击节奏：按基础间隔逐格循环，true 点击、false 跳过，为空时每格都点击
+    pub click_pattern: Vec<bool>,
+    /// 点击频率不超过显示器刷新率
+    pub cap_to_refresh_rate: bool,
+    /// 界面“开始”按钮（及托盘）的启动方式
+    pub button_run_mode: RunMode,
+    /// 热键的启动方式
+    pub hotkey_run_mode: RunMode,
+    /// 开始后到第一次点击之间的随机延迟范围（毫秒），默认 0..0 即立即点击
+    pub start_jitter_min_ms: u64,
+    pub start_jitter_max_ms: u64,
+    /// 固定坐标点击时每次在坐标周围随机偏移的最大像素数
+    pub position_jitter_px: u32,
+    /// 每次点击按住的随机时长范围（毫秒），默认 0..0 即按下后立即松开
+    pub hold_min_ms: u64,
+    pub hold_max_ms: u64,
+    /// 点击间隔上下随机浮动的百分比
+    pub interval_jitter_pct: u32,
+    /// 开始连点时的前台窗口（绑定目标窗口时为目标窗口）失去焦点后自动停止
+    pub stop_on_focus_loss: bool,
+    /// 启用后在 quiet_hours 时段内拒绝开始，运行中进入时段会自动停止
+    pub quiet_hours_enabled: bool,
+    pub quiet_hours: QuietHours,
+    /// 上次看过更新说明的程序版本，升级后据此显示“新版本”面板
+    pub last_seen_version: Option<String>,
+    // 旧版本配置中没有此字段，视为已完成引导
+    #[serde(default = "default_true")]
+    pub first_run_complete: bool,
+}
+
+/// 拟人预设：位置偏移像素、按住时长范围（毫秒）、间隔浮动百分比
+const HUMANIZE_PRESET: (u32, (u64, u64), u32) = (3, (40, 90), 15);
+
+fn default_true() -> bool {
+    true
 }
 
 impl Default for AppConfig {
@@ -159,9 +603,59 @@ impl Default for AppConfig {
                 modifiers: vec![],
                 key: "F1".to_string(),
             },
+            suspend_hotkey: HotkeyConfig {
+                modifiers: vec!["Ctrl".to_string(), "Shift".to_string()],
+                key: "F12".to_string(),
+            },
+            hotkey_revalidate_secs: 30,
+            hotkey_bindings: Vec::new(),
             total_clicks: 0,
+            clicks_since_reset: 0,
             auto_minimize: false,
+            power_saving: false,
+            stats_refresh_ms: 500,
+            pause_freezes_stats: true,
+            command_file_enabled: false,
             hold_mode: false,
+            macro_mode: false,
+            macro_steps: vec![
+                ActionStep::KeyPress { key: "R".to_string() },
+                ActionStep::Wait { ms: 200 },
+                ActionStep::MouseClick { button: MouseButton::Left },
+                ActionStep::Wait { ms: 100 },
+            ],
+            fixed_position: false,
+            click_points: Vec::new(),
+            point_order: PointOrder::Sequential,
+            glide_between_points: false,
+            glide_speed: 2000,
+            reassert_position: false,
+            capture_grid: 0,
+            target_window: None,
+            monitor_profiles: HashMap::new(),
+            suppress_cps_warning: false,
+            cps_warning_threshold: 100,
+            active_profile: None,
+            drag_mode: false,
+            drag_path: vec![ClickPoint::default(); 2],
+            drag_speed: 1000,
+            click_limit: 0,
+            click_pattern: Vec::new(),
+            on_limit: LimitAction::Stop,
+            cap_to_refresh_rate: false,
+            button_run_mode: RunMode::Real,
+            hotkey_run_mode: RunMode::Real,
+            start_jitter_min_ms: 0,
+            start_jitter_max_ms: 0,
+            position_jitter_px: 0,
+            hold_min_ms: 0,
+            hold_max_ms: 0,
+            interval_jitter_pct: 0,
+            stop_on_focus_loss: false,
+            quiet_hours_enabled: false,
+            quiet_hours: QuietHours::default(),
+            last_seen_version: None,
+            first_run_complete: false,
         }
     }
 }
@@ -171,8 +665,8 @@ impl AppConfig {
         Self::default()
     }
 
-    /// 获取配置文件路径
-    pub fn get_config_path() -> Result<PathBuf, String> {
+    /// 获取配置目录，不存在时自动创建
+    pub fn get_config_dir() -> Result<PathBuf, String> {
         let home_dir = dirs::home_dir()
             .ok_or_else(|| "无法获取用户主目录".to_string())?;
 
@@ -184,18 +678,98 @@ impl AppConfig {
                 .map_err(|e| format!("创建配置目录失败: {}", e))?;
         }
 
-        Ok(config_dir.join("config.json"))
+        Ok(config_dir)
+    }
+
+    /// 获取配置文件路径
+    pub fn get_config_path() -> Result<PathBuf, String> {
+        Ok(Self::get_config_dir()?.join("config.json"))
+    }
+
+    /// 获取配置方案目录
+    fn get_profiles_dir() -> Result<PathBuf, String> {
+        let profiles_dir = Self::get_config_dir()?.join("profiles");
+
+        if !profiles_dir.exists() {
+            fs::create_dir_all(&profiles_dir)
+                .map_err(|e| format!("创建配置方案目录失败: {}", e))?;
+        }
+
+        Ok(profiles_dir)
+    }
+
+    fn get_profile_path(name: &str) -> Result<PathBuf, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("配置方案名称不能为空".to_string());
+        }
+        if name.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|']) {
+            return Err("配置方案名称包含非法字符".to_string());
+        }
+
+        Ok(Self::get_profiles_dir()?.join(format!("{}.json", name)))
+    }
+
+    /// 列出已保存的配置方案名称
+    pub fn list_profiles() -> Vec<String> {
+        let Ok(entries) = Self::get_profiles_dir().and_then(|dir| {
+            fs::read_dir(dir).map_err(|e| format!("读取配置方案目录失败: {}", e))
+        }) else {
+            return Vec::new();
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// 将当前配置保存为配置方案
+    pub fn save_profile(&self, name: &str) -> Result<(), String> {
+        let path = Self::get_profile_path(name)?;
+
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("序列化配置失败: {}", e))?;
+
+        fs::write(&path, json)
+            .map_err(|e| format!("写入配置方案失败: {}", e))?;
+
+        crate::log_info!("配置方案已保存到: {}", path.display());
+        Ok(())
+    }
+
+    /// 读取配置方案
+    pub fn load_profile(name: &str) -> Result<Self, String> {
+        let path = Self::get_profile_path(name)?;
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("读取配置方案失败: {}", e))?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| format!("解析配置方案失败: {}", e))
+    }
+
+    /// 删除配置方案
+    pub fn delete_profile(name: &str) -> Result<(), String> {
+        let path = Self::get_profile_path(name)?;
+
+        fs::remove_file(&path)
+            .map_err(|e| format!("删除配置方案失败: {}", e))
     }
 
     /// 从文件加载配置
     pub fn load() -> Self {
         match Self::load_from_file() {
             Ok(config) => {
-                println!("配置加载成功");
+                crate::log_info!("配置加载成功");
                 config
             }
             Err(e) => {
-                println!("配置加载失败，使用默认配置: {}", e);
+                crate::log_warn!("配置加载失败，使用默认配置: {}", e);
                 Self::new()
             }
         }
@@ -208,31 +782,74 @@ impl AppConfig {
             return Err("配置文件不存在".to_string());
         }
 
-        let content = fs::read_to_string(&config_path)
+        Self::load_from(&config_path)
+    }
+
+    /// 从指定路径读取配置
+    pub fn load_from(path: &std::path::Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
             .map_err(|e| format!("读取配置文件失败: {}", e))?;
 
-        let config: AppConfig = serde_json::from_str(&content)
-            .map_err(|e| format!("解析配置文件失败: {}", e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("解析配置文件失败: {}", e))
+    }
+
+    /// 将完整的默认配置写入指定路径，作为手动编辑配置的参考
+    pub fn dump_default(path: &std::path::Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&Self::default())
+            .map_err(|e| format!("序列化配置失败: {}", e))?;
+
+        fs::write(path, json)
+            .map_err(|e| format!("写入示例配置失败: {}", e))?;
 
-        Ok(config)
+        Ok(())
     }
 
     /// 保存配置到文件
     pub fn save(&self) -> Result<(), String> {
         let config_path = Self::get_config_path()?;
+        self.save_to(&config_path)?;
 
+        crate::log_info!("配置已保存到: {}", config_path.display());
+        Ok(())
+    }
+
+    /// 保存配置到指定路径
+    pub fn save_to(&self, path: &std::path::Path) -> Result<(), String> {
         let json = serde_json::to_string_pretty(self)
             .map_err(|e| format!("序列化配置失败: {}", e))?;
 
-        fs::write(&config_path, json)
-            .map_err(|e| format!("写入配置文件失败: {}", e))?;
-
-        println!("配置已保存到: {}", config_path.display());
-        Ok(())
+        fs::write(path, json)
+            .map_err(|e| format!("写入配置文件失败: {}", e))
     }
 }
 
 impl AppConfig {
+    /// 切换到指定显示器布局对应的坐标，返回是否找到匹配的布局
+    ///
+    /// 相对窗口的坐标与显示器布局无关，不做切换
+    pub fn select_monitor_profile(&mut self, signature: &str) -> bool {
+        if self.target_window.is_some() {
+            return false;
+        }
+        match self.monitor_profiles.get(signature) {
+            Some(points) => {
+                self.click_points = points.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 将当前坐标保存到指定显示器布局
+    pub fn store_monitor_profile(&mut self, signature: &str) {
+        if self.target_window.is_some() {
+            return;
+        }
+        self.monitor_profiles
+            .insert(signature.to_string(), self.click_points.clone());
+    }
+
     pub fn get_effective_interval(&self) -> u64 {
         match self.interval_mode {
             IntervalMode::Milliseconds => self.click_interval,
@@ -245,6 +862,213 @@ impl AppConfig {
             }
         }
     }
+
+    /// 启用时返回禁止连点的时间段
+    pub fn active_quiet_hours(&self) -> Option<QuietHours> {
+        self.quiet_hours_enabled.then_some(self.quiet_hours)
+    }
+
+    /// 一次性设置位置、按住时长和间隔三项随机化（拟人预设），关闭时全部清零
+    ///
+    /// 预设只写入各项字段，之后仍可单独调整
+    pub fn apply_humanize_preset(&mut self, enabled: bool) {
+        let (px, hold, pct) = if enabled {
+            HUMANIZE_PRESET
+        } else {
+            (0, (0, 0), 0)
+        };
+        self.position_jitter_px = px;
+        (self.hold_min_ms, self.hold_max_ms) = hold;
+        self.interval_jitter_pct = pct;
+    }
+
+    /// 三项随机化是否与拟人预设完全一致
+    pub fn humanize_preset_active(&self) -> bool {
+        (self.position_jitter_px, (self.hold_min_ms, self.hold_max_ms), self.interval_jitter_pct)
+            == HUMANIZE_PRESET
+    }
+
+    /// 考虑刷新率上限后的实际点击间隔（毫秒）
+    pub fn capped_interval(&self, refresh_hz: Option<u32>) -> u64 {
+        let interval = self.get_effective_interval();
+        match refresh_hz {
+            Some(hz) if self.cap_to_refresh_rate && hz > 0 => interval.max(1000u64.div_ceil(hz as u64)),
+            _ => interval,
+        }
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hotkey(modifiers: &[&str], key: &str) -> HotkeyConfig {
+        HotkeyConfig {
+            modifiers: modifiers.iter().map(|m| m.to_string()).collect(),
+            key: key.to_string(),
+        }
+    }
+
+    #[test]
+    fn display_string_without_modifiers() {
+        assert_eq!(hotkey(&[], "F1").to_display_string(), "F1");
+    }
+
+    #[test]
+    fn display_string_with_multiple_modifiers() {
+        assert_eq!(hotkey(&["Ctrl"], "F1").to_display_string(), "Ctrl+F1");
+        assert_eq!(
+            hotkey(&["Ctrl", "Alt", "Shift"], "A").to_display_string(),
+            "Ctrl+Alt+Shift+A"
+        );
+    }
+
+    #[test]
+    fn display_string_for_double_tap() {
+        assert_eq!(hotkey(&[], "DoubleShift").to_display_string(), "双击Shift");
+    }
+
+    #[test]
+    fn gamepad_buttons_ignore_modifiers() {
+        let pad = hotkey(&["Ctrl"], "PadRB");
+        assert_eq!(pad.gamepad_button(), Some("PadRB"));
+        assert_eq!(pad.to_display_string(), "手柄 RB");
+        assert_eq!(hotkey(&[], "F1").gamepad_button(), None);
+    }
+
+    #[test]
+    fn modifiers_map_to_global_hotkey_flags() {
+        let (modifiers, code) = hotkey(&["Ctrl", "Shift"], "Z").to_global_hotkey().unwrap();
+        assert_eq!(modifiers, Modifiers::CONTROL | Modifiers::SHIFT);
+        assert_eq!(code, Code::KeyZ);
+    }
+
+    #[test]
+    fn unknown_names_are_rejected() {
+        assert!(hotkey(&["Hyper"], "F1").to_global_hotkey().is_err());
+        assert!(hotkey(&[], "F13").to_global_hotkey().is_err());
+        assert!(hotkey(&[], "DoubleCtrl").to_global_hotkey().is_err());
+    }
+
+    #[test]
+    fn refresh_rate_caps_interval_only_when_enabled() {
+        let mut config = AppConfig::new();
+        config.interval_mode = IntervalMode::CPS;
+        config.cps_value = 500;
+        assert_eq!(config.capped_interval(Some(60)), 2);
+
+        config.cap_to_refresh_rate = true;
+        assert_eq!(config.capped_interval(Some(60)), 17);
+        assert_eq!(config.capped_interval(Some(144)), 7);
+        assert_eq!(config.capped_interval(None), 2);
+
+        // 已经慢于刷新率时不受影响
+        config.cps_value = 10;
+        assert_eq!(config.capped_interval(Some(60)), 100);
+    }
+
+    #[test]
+    fn punctuation_keys_map_by_position() {
+        let (_, code) = hotkey(&[], "Semicolon").to_global_hotkey().unwrap();
+        assert_eq!(code, Code::Semicolon);
+        assert_eq!(HotkeyConfig::scan_code("BracketLeft"), Some(0x1A));
+        assert_eq!(HotkeyConfig::scan_code("F1"), None);
+    }
+
+    #[test]
+    fn azerty_layout_registers_the_same_physical_key() {
+        // AZERTY：Q 位置（扫描码 0x10）产生 A，A 位置产生 Q
+        let azerty = |scan: u16| match scan {
+            0x10 => Some(0x41),
+            0x1E => Some(0x51),
+            _ => None,
+        };
+        let (_, code) = hotkey(&["Ctrl"], "Q").to_layout_hotkey(azerty).unwrap();
+        assert_eq!(code, Code::KeyA);
+        let (_, code) = hotkey(&[], "A").to_layout_hotkey(azerty).unwrap();
+        assert_eq!(code, Code::KeyQ);
+    }
+
+    #[test]
+    fn german_layout_maps_umlaut_positions() {
+        // QWERTZ：BracketLeft 位置是 Ü（VK_OEM_1），Semicolon 位置是 Ö（VK_OEM_3），Y/Z 互换
+        let qwertz = |scan: u16| match scan {
+            0x1A => Some(0xBA),
+            0x27 => Some(0xC0),
+            0x15 => Some(0x5A),
+            _ => None,
+        };
+        let (_, code) = hotkey(&[], "BracketLeft").to_layout_hotkey(qwertz).unwrap();
+        assert_eq!(code, Code::Semicolon);
+        let (_, code) = hotkey(&[], "Semicolon").to_layout_hotkey(qwertz).unwrap();
+        assert_eq!(code, Code::Backquote);
+        let (_, code) = hotkey(&[], "Y").to_layout_hotkey(qwertz).unwrap();
+        assert_eq!(code, Code::KeyZ);
+    }
+
+    #[test]
+    fn unmapped_layout_keeps_the_us_code() {
+        let (_, code) = hotkey(&[], "F5").to_layout_hotkey(|_| None).unwrap();
+        assert_eq!(code, Code::F5);
+        let (_, code) = hotkey(&[], "W").to_layout_hotkey(|_| None).unwrap();
+        assert_eq!(code, Code::KeyW);
+    }
+
+    #[test]
+    fn snapping_rounds_to_nearest_grid_point() {
+        let point = ClickPoint { x: 12, y: 18 };
+        assert_eq!(point.snapped(5), ClickPoint { x: 10, y: 20 });
+        assert_eq!(point.snapped(0), point);
+        assert_eq!(point.snapped(1), point);
+        // 副屏在主屏左侧时坐标为负
+        assert_eq!(ClickPoint { x: -3, y: -2 }.snapped(5), ClickPoint { x: -5, y: 0 });
+    }
+
+    #[test]
+    fn window_visibility_is_not_persisted() {
+        let mut config = AppConfig::new();
+        config.window_visible = false;
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("window_visible"));
+
+        // 旧版本写入的隐藏状态会被忽略，总是以可见窗口启动
+        let loaded: AppConfig = serde_json::from_str(r#"{"window_visible": false}"#).unwrap();
+        assert!(loaded.window_visible);
+    }
+
+    #[test]
+    fn quiet_hours_span_midnight() {
+        let night = QuietHours::default();
+        assert!(night.contains(TimeOfDay::new(23, 0)));
+        assert!(night.contains(TimeOfDay::new(2, 30)));
+        assert!(!night.contains(TimeOfDay::new(7, 0)));
+        assert!(!night.contains(TimeOfDay::new(12, 0)));
+
+        let lunch = QuietHours { start: TimeOfDay::new(12, 0), end: TimeOfDay::new(13, 30) };
+        assert!(lunch.contains(TimeOfDay::new(13, 29)));
+        assert!(!lunch.contains(TimeOfDay::new(23, 0)));
 
+        let empty = QuietHours { start: TimeOfDay::new(8, 0), end: TimeOfDay::new(8, 0) };
+        assert!(!empty.contains(TimeOfDay::new(8, 0)));
+    }
+
+    #[test]
+    fn humanize_preset_sets_and_clears_all_three() {
+        let mut config = AppConfig::default();
+        assert!(!config.humanize_preset_active());
+
+        config.apply_humanize_preset(true);
+        assert!(config.humanize_preset_active());
+        assert!(config.position_jitter_px > 0 && config.interval_jitter_pct > 0);
+        assert!(config.hold_min_ms <= config.hold_max_ms && config.hold_max_ms > 0);
+
+        // 手动调整任一项后不再视为预设
+        config.interval_jitter_pct += 5;
+        assert!(!config.humanize_preset_active());
+
+        config.apply_humanize_preset(false);
+        assert_eq!(config.position_jitter_px, 0);
+        assert_eq!((config.hold_min_ms, config.hold_max_ms), (0, 0));
+        assert_eq!(config.interval_jitter_pct, 0);
+    }
+}
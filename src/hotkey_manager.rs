@@ -1,17 +1,109 @@
-use crate::config::HotkeyConfig;
+use crate::config::{BoundAction, HotkeyBinding, HotkeyConfig};
 use global_hotkey::{
     hotkey::HotKey,
-    GlobalHotKeyEvent, GlobalHotKeyManager,
+    GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState,
 };
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
 
 #[cfg(windows)]
-use winapi::um::winuser::GetAsyncKeyState;
+use winapi::um::winuser::{GetAsyncKeyState, MapVirtualKeyW, MAPVK_VSC_TO_VK};
+#[cfg(windows)]
+use windows::Win32::UI::Input::XboxController::{
+    XInputGetState, XINPUT_GAMEPAD_A, XINPUT_GAMEPAD_B, XINPUT_GAMEPAD_BACK,
+    XINPUT_GAMEPAD_BUTTON_FLAGS, XINPUT_GAMEPAD_DPAD_DOWN, XINPUT_GAMEPAD_DPAD_LEFT,
+    XINPUT_GAMEPAD_DPAD_RIGHT, XINPUT_GAMEPAD_DPAD_UP, XINPUT_GAMEPAD_LEFT_SHOULDER,
+    XINPUT_GAMEPAD_LEFT_THUMB, XINPUT_GAMEPAD_RIGHT_SHOULDER, XINPUT_GAMEPAD_RIGHT_THUMB,
+    XINPUT_GAMEPAD_START, XINPUT_GAMEPAD_X, XINPUT_GAMEPAD_Y, XINPUT_STATE, XUSER_MAX_COUNT,
+};
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HotkeyAction {
     Toggle,
     HoldStart,
     HoldStop,
+    /// 热键处理被挂起（true）或恢复（false）
+    SuspendChanged(bool),
+    /// 热键事件失效且重新注册失败，附带错误信息
+    RegistrationLost(String),
+    /// 按下了额外绑定的热键
+    Bound(BoundAction),
+}
+
+/// 长按模式下由按键前后两次的状态得出动作：刚按下时开始，刚松开时停止
+pub fn hold_transition(was_pressed: bool, pressed_now: bool) -> Option<HotkeyAction> {
+    match (was_pressed, pressed_now) {
+        (false, true) => Some(HotkeyAction::HoldStart),
+        (true, false) => Some(HotkeyAction::HoldStop),
+        _ => None,
+    }
+}
+
+/// 单次敲击修饰键的最长按住时间
+const TAP_MAX_HOLD: std::time::Duration = std::time::Duration::from_millis(250);
+/// 两次敲击之间的最大间隔
+const DOUBLE_TAP_WINDOW: std::time::Duration = std::time::Duration::from_millis(400);
+
+/// 轮询检测到按下后，等待对应热键事件的最长时间，超时视为事件丢失
+const EVENT_GRACE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// 长按模式下后台线程轮询按键状态的间隔
+const HOLD_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// 在独立线程中轮询长按热键，按下和松开时通过通道投递 HoldStart/HoldStop
+///
+/// 检测不受界面帧率影响，界面卡顿时也能及时响应，析构时停止线程
+struct HoldPoller {
+    receiver: Receiver<HotkeyAction>,
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl HoldPoller {
+    fn spawn(is_pressed: impl Fn() -> bool + Send + 'static, interval: std::time::Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let running = Arc::new(AtomicBool::new(true));
+
+        let handle = {
+            let running = Arc::clone(&running);
+            thread::spawn(move || {
+                let mut was_pressed = false;
+                while running.load(Ordering::Relaxed) {
+                    let pressed = is_pressed();
+                    if let Some(action) = hold_transition(was_pressed, pressed)
+                        && sender.send(action).is_err()
+                    {
+                        break;
+                    }
+                    was_pressed = pressed;
+                    thread::sleep(interval);
+                }
+            })
+        };
+
+        Self { receiver, running, handle: Some(handle) }
+    }
+}
+
+impl Drop for HoldPoller {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// 双击修饰键检测状态
+#[derive(Default)]
+struct DoubleTapState {
+    was_down: bool,
+    press_start: Option<std::time::Instant>,
+    // 本次按下期间是否混入了其他按键（即正常的组合键使用）
+    dirty: bool,
+    last_tap: Option<std::time::Instant>,
 }
 
 pub struct HotkeyManager {
@@ -19,9 +111,26 @@ pub struct HotkeyManager {
     receiver: Receiver<GlobalHotKeyEvent>,
     toggle_hotkey: Option<HotKey>,
     toggle_hotkey_id: Option<u32>,
+    suspend_hotkey: Option<HotKey>,
+    suspend_hotkey_id: Option<u32>,
+    /// 已注册的额外热键及其动作
+    bindings: Vec<(HotKey, BoundAction)>,
+    suspended: bool,
     is_key_pressed: bool,
     current_hotkey: Option<HotkeyConfig>,
     last_poll_time: std::time::Instant,
+    double_tap: DoubleTapState,
+    /// 长按模式的后台轮询线程，仅在长按模式下存在
+    hold_poller: Option<HoldPoller>,
+    /// 轮询线程检测热键是否按下的方式
+    pressed_probe: Arc<dyn Fn(&HotkeyConfig) -> bool + Send + Sync>,
+    /// 重新校验热键注册的间隔，None 表示不校验
+    revalidate_interval: Option<std::time::Duration>,
+    last_revalidate: std::time::Instant,
+    /// 轮询检测到按下、但尚未收到热键事件的时间
+    pending_poll_press: Option<std::time::Instant>,
+    /// 上次校验以来丢失的热键事件数
+    missed_events: u32,
 }
 
 impl HotkeyManager {
@@ -38,20 +147,46 @@ impl HotkeyManager {
             receiver,
             toggle_hotkey: None,
             toggle_hotkey_id: None,
+            suspend_hotkey: None,
+            suspend_hotkey_id: None,
+            bindings: Vec::new(),
+            suspended: false,
             is_key_pressed: false,
             current_hotkey: None,
             last_poll_time: std::time::Instant::now(),
+            double_tap: DoubleTapState::default(),
+            hold_poller: None,
+            pressed_probe: Arc::new(is_hotkey_pressed),
+            revalidate_interval: None,
+            last_revalidate: std::time::Instant::now(),
+            pending_poll_press: None,
+            missed_events: 0,
         })
     }
 
     pub fn update_hotkeys(
         &mut self,
         toggle_config: &HotkeyConfig,
+        suspend_config: &HotkeyConfig,
     ) -> Result<(), String> {
+        let toggle_result = self.register_toggle_hotkey(toggle_config);
+        // 挂起热键冲突时不影响切换热键，记录后在没有挂起热键的情况下继续运行
+        if let Err(e) = self.register_suspend_hotkey(suspend_config, toggle_config) {
+            crate::log_warn!("{}，挂起热键未启用", e);
+        }
+        toggle_result
+    }
+
+    /// 挂起热键是否已成功注册
+    pub fn suspend_hotkey_active(&self) -> bool {
+        self.suspend_hotkey.is_some()
+    }
+
+    fn register_toggle_hotkey(&mut self, toggle_config: &HotkeyConfig) -> Result<(), String> {
         // 先注销旧的热键
         if let Some(old_hotkey) = self.toggle_hotkey.take() {
             if let Err(e) = self.manager.unregister(old_hotkey) {
-                eprintln!("注销旧热键失败: {}", e);
+                crate::log_warn!("注销旧热键失败: {}", e);
             }
         }
         self.toggle_hotkey_id = None;
@@ -59,8 +194,14 @@ impl HotkeyManager {
         // 重置按键状态
         self.reset_key_state();
 
+        // 仅修饰键和手柄按键无法通过系统注册，改用轮询检测
+        if toggle_config.double_tap_modifier().is_some() || toggle_config.gamepad_button().is_some() {
+            self.current_hotkey = Some(toggle_config.clone());
+            return Ok(());
+        }
+
         // 注册新的切换热键
-        match toggle_config.to_global_hotkey() {
+        match toggle_config.to_layout_hotkey(layout_vk) {
             Ok((modifiers, code)) => {
                 let hotkey = HotKey::new(Some(modifiers), code);
                 match self.manager.register(hotkey) {
@@ -84,38 +225,231 @@ impl HotkeyManager {
         }
     }
 
+    fn register_suspend_hotkey(
+        &mut self,
+        suspend_config: &HotkeyConfig,
+        toggle_config: &HotkeyConfig,
+    ) -> Result<(), String> {
+        if let Some(old_hotkey) = self.suspend_hotkey.take()
+            && let Err(e) = self.manager.unregister(old_hotkey)
+        {
+            crate::log_warn!("注销旧挂起热键失败: {}", e);
+        }
+        self.suspend_hotkey_id = None;
+
+        if suspend_config.double_tap_modifier().is_some() {
+            return Err("挂起热键不支持仅修饰键".to_string());
+        }
+        if suspend_config.gamepad_button().is_some() {
+            return Err("挂起热键不支持手柄按键".to_string());
+        }
+        if suspend_config.to_display_string() == toggle_config.to_display_string() {
+            return Err("挂起热键不能与切换热键相同".to_string());
+        }
+
+        let (modifiers, code) = suspend_config
+            .to_layout_hotkey(layout_vk)
+            .map_err(|e| format!("挂起热键配置错误: {}", e))?;
+        let hotkey = HotKey::new(Some(modifiers), code);
+        self.manager.register(hotkey).map_err(|_| {
+            format!(
+                "挂起热键 {} 已被占用，请尝试其他组合",
+                suspend_config.to_display_string()
+            )
+        })?;
+        self.suspend_hotkey_id = Some(hotkey.id());
+        self.suspend_hotkey = Some(hotkey);
+        Ok(())
+    }
+
+    /// 重新注册全部额外热键，需在 update_hotkeys 之后调用以检查冲突
+    ///
+    /// 单个热键注册失败不影响其他热键，错误合并后返回
+    pub fn update_bindings(&mut self, bindings: &[HotkeyBinding]) -> Result<(), String> {
+        for (hotkey, _) in self.bindings.drain(..) {
+            if let Err(e) = self.manager.unregister(hotkey) {
+                crate::log_warn!("注销额外热键失败: {}", e);
+            }
+        }
+
+        let mut errors = Vec::new();
+        for binding in bindings {
+            let name = binding.hotkey.to_display_string();
+            if binding.hotkey.double_tap_modifier().is_some() {
+                errors.push(format!("额外热键 {} 不支持仅修饰键", name));
+                continue;
+            }
+            if binding.hotkey.gamepad_button().is_some() {
+                errors.push(format!("额外热键 {} 不支持手柄按键", name));
+                continue;
+            }
+            let (modifiers, code) = match binding.hotkey.to_layout_hotkey(layout_vk) {
+                Ok(parts) => parts,
+                Err(e) => {
+                    errors.push(format!("额外热键配置错误: {}", e));
+                    continue;
+                }
+            };
+            let hotkey = HotKey::new(Some(modifiers), code);
+            let id = Some(hotkey.id());
+            if id == self.toggle_hotkey_id
+                || id == self.suspend_hotkey_id
+                || self.bindings.iter().any(|(bound, _)| bound.id() == hotkey.id())
+            {
+                errors.push(format!("额外热键 {} 与其他热键重复", name));
+                continue;
+            }
+            match self.manager.register(hotkey) {
+                Ok(()) => self.bindings.push((hotkey, binding.action.clone())),
+                Err(_) => errors.push(format!("额外热键 {} 已被占用", name)),
+            }
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors.join("\n")) }
+    }
+
+    /// 热键处理是否已挂起
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// 设置热键失效检测的间隔，为 0 时关闭检测
+    pub fn set_revalidate_interval(&mut self, interval: std::time::Duration) {
+        self.revalidate_interval = (!interval.is_zero()).then_some(interval);
+        self.last_revalidate = std::time::Instant::now();
+        self.missed_events = 0;
+    }
+
+    /// 记录热键事件是否丢失，并按间隔尝试重新注册丢失事件的切换热键
+    ///
+    /// 其他程序抢占热键后系统不再投递事件，只有轮询还能检测到按键
+    fn revalidate(&mut self, event_received: bool) -> Option<HotkeyAction> {
+        let now = std::time::Instant::now();
+        if event_received {
+            self.pending_poll_press = None;
+        } else if self
+            .pending_poll_press
+            .is_some_and(|pressed| now.duration_since(pressed) >= EVENT_GRACE)
+        {
+            self.pending_poll_press = None;
+            self.missed_events += 1;
+        }
+
+        let interval = self.revalidate_interval?;
+        if now.duration_since(self.last_revalidate) < interval {
+            return None;
+        }
+        self.last_revalidate = now;
+        if self.missed_events == 0 {
+            return None;
+        }
+        self.missed_events = 0;
+
+        let config = self.current_hotkey.clone()?;
+        match self.register_toggle_hotkey(&config) {
+            Ok(()) => {
+                crate::log_info!("热键 {} 事件丢失，已重新注册", config.to_display_string());
+                None
+            }
+            Err(e) => {
+                crate::log_error!("热键 {} 重新注册失败: {}", config.to_display_string(), e);
+                Some(HotkeyAction::RegistrationLost(e))
+            }
+        }
+    }
+
     /// 重置按键状态，用于模式切换时清除旧状态
     pub fn reset_key_state(&mut self) {
         self.is_key_pressed = false;
+        self.double_tap = DoubleTapState::default();
+        // 轮询线程按旧热键检测，下次需要时按当前热键重新创建
+        self.hold_poller = None;
         // 清空事件队列
         while self.receiver.try_recv().is_ok() {}
     }
 
+    /// 替换轮询时检测热键是否按下的方式，测试中用于模拟按键
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn set_pressed_probe(&mut self, probe: impl Fn(&HotkeyConfig) -> bool + Send + Sync + 'static) {
+        self.pressed_probe = Arc::new(probe);
+        self.hold_poller = None;
+    }
+
     pub fn check_events(&mut self, hold_mode: bool) -> Option<HotkeyAction> {
-        if hold_mode {
-            // 长按模式：使用轮询检测按键状态
+        // 先取出全部事件，挂起热键优先处理
+        let events: Vec<GlobalHotKeyEvent> = self.receiver.try_iter().collect();
+        let suspend_pressed = events.iter().any(|event| {
+            Some(event.id) == self.suspend_hotkey_id && matches!(event.state, HotKeyState::Pressed)
+        });
+        if suspend_pressed {
+            self.suspended = !self.suspended;
+            self.is_key_pressed = false;
+            self.double_tap = DoubleTapState::default();
+            return Some(HotkeyAction::SuspendChanged(self.suspended));
+        }
+
+        // 挂起期间除恢复热键外不响应任何按键
+        if self.suspended {
+            if let Some(poller) = &self.hold_poller {
+                while poller.receiver.try_recv().is_ok() {}
+            }
+            return None;
+        }
+
+        let bound = events
+            .iter()
+            .filter(|event| matches!(event.state, HotKeyState::Pressed))
+            .find_map(|event| self.bindings.iter().find(|(hotkey, _)| hotkey.id() == event.id));
+        if let Some((_, action)) = bound {
+            return Some(HotkeyAction::Bound(action.clone()));
+        }
+
+        let double_tap_modifier = self
+            .current_hotkey
+            .as_ref()
+            .and_then(|config| config.double_tap_modifier())
+            .map(str::to_string);
+        if let Some(modifier) = double_tap_modifier {
+            // 双击修饰键：无论触发模式，均作为切换处理
             let now = std::time::Instant::now();
             if now.duration_since(self.last_poll_time) < std::time::Duration::from_millis(10) {
-                // 提高轮询频率到 100Hz，确保响应及时
                 return None;
             }
             self.last_poll_time = now;
-            
-            // 清空事件队列防止堆积
-            while self.receiver.try_recv().is_ok() {}
-            
-            return self.check_key_hold_state();
+
+            return self.check_double_tap(&modifier, now);
         }
 
+        let gamepad = self
+            .current_hotkey
+            .as_ref()
+            .is_some_and(|config| config.gamepad_button().is_some());
+        if hold_mode || gamepad {
+            // 长按模式：由后台线程以 100Hz 轮询按键状态，这里只取出结果。
+            // 事件队列已在上方清空，长按只依赖轮询
+            let poller = self.hold_poller.get_or_insert_with(|| {
+                let hotkey = self.current_hotkey.clone();
+                let probe = Arc::clone(&self.pressed_probe);
+                HoldPoller::spawn(move || hotkey.as_ref().is_some_and(|hotkey| probe(hotkey)), HOLD_POLL_INTERVAL)
+            });
+            let action = poller.receiver.try_recv().ok();
+            if hold_mode {
+                return action;
+            }
+            // 手柄按键没有系统事件，切换模式同样依赖轮询：按下时切换，忽略松开
+            return action.filter(|action| *action == HotkeyAction::HoldStart).map(|_| HotkeyAction::Toggle);
+        }
+        self.hold_poller = None;
+
         // 切换模式：事件驱动 + 轮询双保险
         // 先检查事件队列
-        let mut event_triggered = false;
-        while let Ok(event) = self.receiver.try_recv() {
-            if Some(event.id) == self.toggle_hotkey_id {
-                event_triggered = true;
-            }
+        let event_triggered = events
+            .iter()
+            .any(|event| Some(event.id) == self.toggle_hotkey_id);
+        if let Some(action) = self.revalidate(event_triggered) {
+            return Some(action);
         }
-        
+
         if event_triggered {
             // 事件触发时，检查是否是新的按下（防止重复触发）
             let pressed_now = self.is_key_currently_pressed();
@@ -134,8 +468,9 @@ impl HotkeyManager {
                 
                 let pressed_now = self.is_key_currently_pressed();
                 if pressed_now && !self.is_key_pressed {
-                    // 按下边沿：触发切换
+                    // 按下边沿：触发切换，并等待确认热键事件是否随后到达
                     self.is_key_pressed = true;
+                    self.pending_poll_press = Some(now);
                     return Some(HotkeyAction::Toggle);
                 } else if !pressed_now && self.is_key_pressed {
                     // 松开：重置状态
@@ -147,102 +482,237 @@ impl HotkeyManager {
         None
     }
 
-    fn check_key_hold_state(&mut self) -> Option<HotkeyAction> {
-        // 使用Windows API检查按键状态
-        let is_currently_pressed = self.is_key_currently_pressed();
+    fn check_double_tap(&mut self, modifier: &str, now: std::time::Instant) -> Option<HotkeyAction> {
+        let down = Self::is_modifier_pressed(modifier);
+        let other_pressed = Self::is_other_key_pressed(modifier);
+        let state = &mut self.double_tap;
 
-        if is_currently_pressed && !self.is_key_pressed {
-            // 按键刚被按下
-            self.is_key_pressed = true;
-            return Some(HotkeyAction::HoldStart);
-        } else if !is_currently_pressed && self.is_key_pressed {
-            // 按键刚被松开
-            self.is_key_pressed = false;
-            return Some(HotkeyAction::HoldStop);
+        if down && !state.was_down {
+            // 修饰键按下
+            state.was_down = true;
+            state.press_start = Some(now);
+            state.dirty = other_pressed;
+        } else if down {
+            // 按住期间按下其他键，视为普通组合键
+            state.dirty |= other_pressed;
+        } else if state.was_down {
+            // 修饰键松开，判断是否为一次干净的敲击
+            state.was_down = false;
+            let is_tap = !state.dirty
+                && state
+                    .press_start
+                    .is_some_and(|start| now.duration_since(start) <= TAP_MAX_HOLD);
+
+            if !is_tap {
+                state.last_tap = None;
+            } else if state
+                .last_tap
+                .is_some_and(|last| now.duration_since(last) <= DOUBLE_TAP_WINDOW)
+            {
+                state.last_tap = None;
+                return Some(HotkeyAction::Toggle);
+            } else {
+                state.last_tap = Some(now);
+            }
+        } else if other_pressed {
+            // 两次敲击之间按下其他键，取消双击
+            state.last_tap = None;
         }
 
         None
     }
 
     #[cfg(windows)]
+    fn is_modifier_pressed(modifier: &str) -> bool {
+        let codes: &[i32] = match modifier {
+            "Ctrl" => &[0x11, 0xA2, 0xA3],
+            "Alt" => &[0x12, 0xA4, 0xA5],
+            "Shift" => &[0x10, 0xA0, 0xA1],
+            _ => &[],
+        };
+        codes.iter().any(|&vk| unsafe { GetAsyncKeyState(vk) < 0 })
+    }
+
+    #[cfg(not(windows))]
+    fn is_modifier_pressed(_modifier: &str) -> bool {
+        false
+    }
+
+    /// 检查除目标修饰键与鼠标按键外，是否有其他按键被按下
+    #[cfg(windows)]
+    fn is_other_key_pressed(modifier: &str) -> bool {
+        let own: &[i32] = match modifier {
+            "Ctrl" => &[0x11, 0xA2, 0xA3],
+            "Alt" => &[0x12, 0xA4, 0xA5],
+            "Shift" => &[0x10, 0xA0, 0xA1],
+            _ => &[],
+        };
+        // 跳过鼠标按键（0x01-0x06），避免连点过程中的模拟点击干扰检测
+        (0x08..=0xFE)
+            .filter(|vk| !own.contains(vk))
+            .any(|vk| unsafe { GetAsyncKeyState(vk) < 0 })
+    }
+
+    #[cfg(not(windows))]
+    fn is_other_key_pressed(_modifier: &str) -> bool {
+        false
+    }
+
     fn is_key_currently_pressed(&self) -> bool {
-        if let Some(ref hotkey_config) = self.current_hotkey {
-            // 检查主按键
-            let key_pressed = match hotkey_config.key.as_str() {
-                "F1" => unsafe { GetAsyncKeyState(0x70) < 0 },
-                "F2" => unsafe { GetAsyncKeyState(0x71) < 0 },
-                "F3" => unsafe { GetAsyncKeyState(0x72) < 0 },
-                "F4" => unsafe { GetAsyncKeyState(0x73) < 0 },
-                "F5" => unsafe { GetAsyncKeyState(0x74) < 0 },
-                "F6" => unsafe { GetAsyncKeyState(0x75) < 0 },
-                "F7" => unsafe { GetAsyncKeyState(0x76) < 0 },
-                "F8" => unsafe { GetAsyncKeyState(0x77) < 0 },
-                "F9" => unsafe { GetAsyncKeyState(0x78) < 0 },
-                "F10" => unsafe { GetAsyncKeyState(0x79) < 0 },
-                "F11" => unsafe { GetAsyncKeyState(0x7A) < 0 },
-                "F12" => unsafe { GetAsyncKeyState(0x7B) < 0 },
-                "Space" => unsafe { GetAsyncKeyState(0x20) < 0 },
-                "Enter" => unsafe { GetAsyncKeyState(0x0D) < 0 },
-                "Esc" => unsafe { GetAsyncKeyState(0x1B) < 0 },
-                "Tab" => unsafe { GetAsyncKeyState(0x09) < 0 },
-                key if key.len() == 1 => {
-                    let ch = key.chars().next().unwrap().to_ascii_uppercase();
-                    if ch.is_ascii_alphabetic() {
-                        unsafe { GetAsyncKeyState(ch as i32) < 0 }
-                    } else {
-                        false
-                    }
-                }
-                _ => false,
-            };
+        self.current_hotkey.as_ref().is_some_and(is_hotkey_pressed)
+    }
+}
 
-            if !key_pressed {
-                return false;
-            }
+/// 热键的主按键和全部修饰键当前是否都处于按下状态
+#[cfg(windows)]
+fn is_hotkey_pressed(hotkey_config: &HotkeyConfig) -> bool {
+    if let Some(button) = hotkey_config.gamepad_button() {
+        return is_gamepad_button_pressed(button);
+    }
 
-            // 检查修饰键（兼容左右键）
-            for modifier in &hotkey_config.modifiers {
-                let modifier_pressed = match modifier.as_str() {
-                    // Ctrl: VK_CONTROL, VK_LCONTROL(0xA2), VK_RCONTROL(0xA3)
-                    "Ctrl" => unsafe {
-                        (GetAsyncKeyState(0x11) < 0) || (GetAsyncKeyState(0xA2) < 0) || (GetAsyncKeyState(0xA3) < 0)
-                    },
-                    // Alt: VK_MENU, VK_LMENU(0xA4), VK_RMENU(0xA5)
-                    "Alt" => unsafe {
-                        (GetAsyncKeyState(0x12) < 0) || (GetAsyncKeyState(0xA4) < 0) || (GetAsyncKeyState(0xA5) < 0)
-                    },
-                    // Shift: VK_SHIFT, VK_LSHIFT(0xA0), VK_RSHIFT(0xA1)
-                    "Shift" => unsafe {
-                        (GetAsyncKeyState(0x10) < 0) || (GetAsyncKeyState(0xA0) < 0) || (GetAsyncKeyState(0xA1) < 0)
-                    },
-                    // Win: VK_LWIN(0x5B), VK_RWIN(0x5C)
-                    "Win" => unsafe {
-                        (GetAsyncKeyState(0x5B) < 0) || (GetAsyncKeyState(0x5C) < 0)
-                    },
-                    _ => false,
-                };
-                if !modifier_pressed {
-                    return false;
-                }
-            }
+    // 检查主按键
+    let key_pressed = match hotkey_config.key.as_str() {
+        "F1" => unsafe { GetAsyncKeyState(0x70) < 0 },
+        "F2" => unsafe { GetAsyncKeyState(0x71) < 0 },
+        "F3" => unsafe { GetAsyncKeyState(0x72) < 0 },
+        "F4" => unsafe { GetAsyncKeyState(0x73) < 0 },
+        "F5" => unsafe { GetAsyncKeyState(0x74) < 0 },
+        "F6" => unsafe { GetAsyncKeyState(0x75) < 0 },
+        "F7" => unsafe { GetAsyncKeyState(0x76) < 0 },
+        "F8" => unsafe { GetAsyncKeyState(0x77) < 0 },
+        "F9" => unsafe { GetAsyncKeyState(0x78) < 0 },
+        "F10" => unsafe { GetAsyncKeyState(0x79) < 0 },
+        "F11" => unsafe { GetAsyncKeyState(0x7A) < 0 },
+        "F12" => unsafe { GetAsyncKeyState(0x7B) < 0 },
+        "Space" => unsafe { GetAsyncKeyState(0x20) < 0 },
+        "Enter" => unsafe { GetAsyncKeyState(0x0D) < 0 },
+        "Esc" => unsafe { GetAsyncKeyState(0x1B) < 0 },
+        "Tab" => unsafe { GetAsyncKeyState(0x09) < 0 },
+        // 字母和符号键按物理位置检测，与键盘布局无关
+        key => match HotkeyConfig::scan_code(key).and_then(layout_vk) {
+            Some(vk) => unsafe { GetAsyncKeyState(vk as i32) < 0 },
+            None => false,
+        },
+    };
+
+    if !key_pressed {
+        return false;
+    }
 
-            true
-        } else {
-            false
+    // 检查修饰键（兼容左右键）
+    for modifier in &hotkey_config.modifiers {
+        let modifier_pressed = match modifier.as_str() {
+            // Ctrl: VK_CONTROL, VK_LCONTROL(0xA2), VK_RCONTROL(0xA3)
+            "Ctrl" => unsafe {
+                (GetAsyncKeyState(0x11) < 0) || (GetAsyncKeyState(0xA2) < 0) || (GetAsyncKeyState(0xA3) < 0)
+            },
+            // Alt: VK_MENU, VK_LMENU(0xA4), VK_RMENU(0xA5)
+            "Alt" => unsafe {
+                (GetAsyncKeyState(0x12) < 0) || (GetAsyncKeyState(0xA4) < 0) || (GetAsyncKeyState(0xA5) < 0)
+            },
+            // Shift: VK_SHIFT, VK_LSHIFT(0xA0), VK_RSHIFT(0xA1)
+            "Shift" => unsafe {
+                (GetAsyncKeyState(0x10) < 0) || (GetAsyncKeyState(0xA0) < 0) || (GetAsyncKeyState(0xA1) < 0)
+            },
+            // Win: VK_LWIN(0x5B), VK_RWIN(0x5C)
+            "Win" => unsafe {
+                (GetAsyncKeyState(0x5B) < 0) || (GetAsyncKeyState(0x5C) < 0)
+            },
+            _ => false,
+        };
+        if !modifier_pressed {
+            return false;
         }
     }
 
-    #[cfg(not(windows))]
-    fn is_key_currently_pressed(&self) -> bool {
-        false
+    true
+}
+
+#[cfg(not(windows))]
+fn is_hotkey_pressed(_hotkey_config: &HotkeyConfig) -> bool {
+    false
+}
+
+/// 任一已连接的 XInput 手柄上是否按下了该按键
+#[cfg(windows)]
+fn is_gamepad_button_pressed(button: &str) -> bool {
+    let mask = match button {
+        "PadA" => XINPUT_GAMEPAD_A,
+        "PadB" => XINPUT_GAMEPAD_B,
+        "PadX" => XINPUT_GAMEPAD_X,
+        "PadY" => XINPUT_GAMEPAD_Y,
+        "PadLB" => XINPUT_GAMEPAD_LEFT_SHOULDER,
+        "PadRB" => XINPUT_GAMEPAD_RIGHT_SHOULDER,
+        "PadLS" => XINPUT_GAMEPAD_LEFT_THUMB,
+        "PadRS" => XINPUT_GAMEPAD_RIGHT_THUMB,
+        "PadStart" => XINPUT_GAMEPAD_START,
+        "PadBack" => XINPUT_GAMEPAD_BACK,
+        "PadUp" => XINPUT_GAMEPAD_DPAD_UP,
+        "PadDown" => XINPUT_GAMEPAD_DPAD_DOWN,
+        "PadLeft" => XINPUT_GAMEPAD_DPAD_LEFT,
+        "PadRight" => XINPUT_GAMEPAD_DPAD_RIGHT,
+        _ => XINPUT_GAMEPAD_BUTTON_FLAGS(0),
+    };
+    (0..XUSER_MAX_COUNT).any(|index| {
+        let mut state = XINPUT_STATE::default();
+        // 未连接的手柄返回 ERROR_DEVICE_NOT_CONNECTED
+        let connected = unsafe { XInputGetState(index, &mut state) } == 0;
+        connected && state.Gamepad.wButtons.0 & mask.0 != 0
+    })
+}
+
+/// 扫描码在当前键盘布局下对应的虚拟键码
+#[cfg(windows)]
+fn layout_vk(scan: u16) -> Option<u16> {
+    match unsafe { MapVirtualKeyW(scan as u32, MAPVK_VSC_TO_VK) } {
+        0 => None,
+        vk => Some(vk as u16),
     }
 }
 
+#[cfg(not(windows))]
+fn layout_vk(_scan: u16) -> Option<u16> {
+    None
+}
+
 impl Drop for HotkeyManager {
     fn drop(&mut self) {
         // 程序退出时注销所有热键
         if let Some(hotkey) = self.toggle_hotkey.take() {
             let _ = self.manager.unregister(hotkey);
         }
+        if let Some(hotkey) = self.suspend_hotkey.take() {
+            let _ = self.manager.unregister(hotkey);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::RecvTimeoutError;
+    use std::time::Duration;
+
+    #[test]
+    fn hold_poller_delivers_press_and_release_over_channel() {
+        let pressed = Arc::new(AtomicBool::new(false));
+        let poller = {
+            let pressed = Arc::clone(&pressed);
+            HoldPoller::spawn(move || pressed.load(Ordering::Relaxed), Duration::from_millis(1))
+        };
+        let timeout = Duration::from_secs(1);
+
+        assert_eq!(poller.receiver.recv_timeout(Duration::from_millis(20)), Err(RecvTimeoutError::Timeout));
+        pressed.store(true, Ordering::Relaxed);
+        assert_eq!(poller.receiver.recv_timeout(timeout), Ok(HotkeyAction::HoldStart));
+        // 按住期间不重复投递
+        assert_eq!(poller.receiver.recv_timeout(Duration::from_millis(20)), Err(RecvTimeoutError::Timeout));
+        pressed.store(false, Ordering::Relaxed);
+        assert_eq!(poller.receiver.recv_timeout(timeout), Ok(HotkeyAction::HoldStop));
+
+        // 析构时线程退出
+        let started = std::time::Instant::now();
+        drop(poller);
+        assert!(started.elapsed() < Duration::from_millis(100));
     }
 }
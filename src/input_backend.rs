@@ -0,0 +1,379 @@
+use crate::config::{ClickPoint, MouseButton, ScrollDirection};
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, INPUT_MOUSE, KEYBDINPUT, KEYBD_EVENT_FLAGS,
+    KEYEVENTF_KEYUP, MOUSEEVENTF_ABSOLUTE, MOUSEEVENTF_HWHEEL, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP,
+    MOUSEEVENTF_MOVE, MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEEVENTF_VIRTUALDESK,
+    MOUSEEVENTF_WHEEL,
+    MOUSEINPUT, MOUSE_EVENT_FLAGS, VIRTUAL_KEY,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, SetCursorPos, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_REMOTESESSION,
+    SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, WHEEL_DELTA,
+};
+
+/// 一次完整点击包含的事件数（按下 + 松开）
+pub const CLICK_EVENT_COUNT: u32 = 2;
+
+/// 在指定位置点击时应注入的事件数（带坐标时额外包含一次移动）
+pub fn expected_click_events(position: Option<ClickPoint>) -> u32 {
+    if position.is_some() {
+        CLICK_EVENT_COUNT + 1
+    } else {
+        CLICK_EVENT_COUNT
+    }
+}
+
+/// 当前是否运行在远程桌面会话中
+///
+/// 远程会话的输入桌面由客户端接管，注入的点击可能被静默丢弃
+pub fn is_remote_session() -> bool {
+    unsafe { GetSystemMetrics(SM_REMOTESESSION) != 0 }
+}
+
+/// 输入事件注入后端
+pub trait InputBackend: Send + Sync {
+    /// 发送一次点击，指定坐标时先移动到该位置，返回实际注入成功的事件数
+    fn click(&self, button: MouseButton, position: Option<ClickPoint>) -> u32;
+
+    /// 单独按下或松开鼠标按键，用于拖拽，返回是否注入成功
+    fn button(&self, button: MouseButton, down: bool) -> bool;
+
+    /// 按下并松开一个键，返回实际注入成功的事件数
+    fn key_press(&self, vk: u16) -> u32;
+
+    /// 将光标移动到屏幕坐标
+    fn move_to(&self, x: i32, y: i32) -> bool;
+
+    /// 滚动一格滚轮，positive 为 true 时向上/向右，返回是否注入成功
+    fn scroll(&self, direction: ScrollDirection, positive: bool) -> bool;
+}
+
+/// 基于 SendInput 的 Windows 注入后端
+pub struct SendInputBackend;
+
+impl SendInputBackend {
+    fn mouse_input(flags: MOUSE_EVENT_FLAGS) -> INPUT {
+        Self::mouse_input_at(flags, 0, 0)
+    }
+
+    fn mouse_input_at(flags: MOUSE_EVENT_FLAGS, dx: i32, dy: i32) -> INPUT {
+        INPUT {
+            r#type: INPUT_MOUSE,
+            Anonymous: INPUT_0 {
+                mi: MOUSEINPUT {
+                    dx,
+                    dy,
+                    mouseData: 0,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        }
+    }
+
+    /// 生成移动到屏幕坐标的绝对移动事件（坐标归一化到整个虚拟桌面）
+    fn absolute_move(point: ClickPoint) -> INPUT {
+        let (left, top, width, height) = unsafe {
+            (
+                GetSystemMetrics(SM_XVIRTUALSCREEN),
+                GetSystemMetrics(SM_YVIRTUALSCREEN),
+                GetSystemMetrics(SM_CXVIRTUALSCREEN).max(2),
+                GetSystemMetrics(SM_CYVIRTUALSCREEN).max(2),
+            )
+        };
+        let dx = ((point.x - left) as i64 * 65535 / (width - 1) as i64) as i32;
+        let dy = ((point.y - top) as i64 * 65535 / (height - 1) as i64) as i32;
+        Self::mouse_input_at(
+            MOUSEEVENTF_MOVE | MOUSEEVENTF_ABSOLUTE | MOUSEEVENTF_VIRTUALDESK,
+            dx,
+            dy,
+        )
+    }
+
+    fn key_input(vk: u16, flags: KEYBD_EVENT_FLAGS) -> INPUT {
+        INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: VIRTUAL_KEY(vk),
+                    wScan: 0,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        }
+    }
+}
+
+impl InputBackend for SendInputBackend {
+    fn click(&self, button: MouseButton, position: Option<ClickPoint>) -> u32 {
+        let (down, up) = match button {
+            MouseButton::Left => (MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP),
+            MouseButton::Right => (MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP),
+        };
+
+        // 移动与点击放在同一批事件中，避免多线程时被其他点击插入
+        let mut inputs = Vec::with_capacity(3);
+        if let Some(point) = position {
+            inputs.push(Self::absolute_move(point));
+        }
+        inputs.push(Self::mouse_input(down));
+        inputs.push(Self::mouse_input(up));
+
+        // SendInput 返回实际插入输入流的事件数，被 UIPI 或沙箱拦截时为 0
+        unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) }
+    }
+
+    fn button(&self, button: MouseButton, down: bool) -> bool {
+        let flags = match (button, down) {
+            (MouseButton::Left, true) => MOUSEEVENTF_LEFTDOWN,
+            (MouseButton::Left, false) => MOUSEEVENTF_LEFTUP,
+            (MouseButton::Right, true) => MOUSEEVENTF_RIGHTDOWN,
+            (MouseButton::Right, false) => MOUSEEVENTF_RIGHTUP,
+        };
+        let inputs = [Self::mouse_input(flags)];
+        unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) == 1 }
+    }
+
+    fn key_press(&self, vk: u16) -> u32 {
+        let inputs = [
+            Self::key_input(vk, KEYBD_EVENT_FLAGS(0)),
+            Self::key_input(vk, KEYEVENTF_KEYUP),
+        ];
+        unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) }
+    }
+
+    fn move_to(&self, x: i32, y: i32) -> bool {
+        unsafe { SetCursorPos(x, y).is_ok() }
+    }
+
+    fn scroll(&self, direction: ScrollDirection, positive: bool) -> bool {
+        let flags = match direction {
+            ScrollDirection::Vertical => MOUSEEVENTF_WHEEL,
+            ScrollDirection::Horizontal => MOUSEEVENTF_HWHEEL,
+        };
+        // mouseData 是有符号的滚动量，以 u32 形式传入
+        let delta = if positive { WHEEL_DELTA as i32 } else { -(WHEEL_DELTA as i32) };
+        let mut input = Self::mouse_input(flags);
+        input.Anonymous.mi.mouseData = delta as u32;
+        unsafe { SendInput(&[input], std::mem::size_of::<INPUT>() as i32) == 1 }
+    }
+}
+
+/// 点击间隔的累计统计（Welford 算法，内存占用固定）
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IntervalStats {
+    last: Option<std::time::Instant>,
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl IntervalStats {
+    fn record(&mut self, now: std::time::Instant) {
+        if let Some(last) = self.last {
+            let sample = now.duration_since(last).as_secs_f64() * 1000.0;
+            self.count += 1;
+            let delta = sample - self.mean;
+            self.mean += delta / self.count as f64;
+            self.m2 += delta * (sample - self.mean);
+        }
+        self.last = Some(now);
+    }
+
+    /// 平均点击间隔（毫秒）
+    pub fn mean_ms(&self) -> f64 {
+        self.mean
+    }
+
+    /// 点击间隔的标准差（毫秒），即抖动
+    pub fn jitter_ms(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            (self.m2 / (self.count - 1) as f64).sqrt()
+        }
+    }
+}
+
+/// 空跑后端：只统计点击时间，不产生任何真实输入
+pub struct DryRunBackend {
+    stats: std::sync::Mutex<IntervalStats>,
+}
+
+impl DryRunBackend {
+    pub fn new() -> Self {
+        Self {
+            stats: std::sync::Mutex::new(IntervalStats::default()),
+        }
+    }
+
+    pub fn interval_stats(&self) -> IntervalStats {
+        *self.stats.lock().unwrap()
+    }
+}
+
+impl Default for DryRunBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputBackend for DryRunBackend {
+    fn click(&self, _button: MouseButton, position: Option<ClickPoint>) -> u32 {
+        self.stats.lock().unwrap().record(std::time::Instant::now());
+        expected_click_events(position)
+    }
+
+    fn button(&self, _button: MouseButton, _down: bool) -> bool {
+        true
+    }
+
+    fn key_press(&self, _vk: u16) -> u32 {
+        2
+    }
+
+    fn move_to(&self, _x: i32, _y: i32) -> bool {
+        true
+    }
+
+    fn scroll(&self, _direction: ScrollDirection, _positive: bool) -> bool {
+        true
+    }
+}
+
+/// 模拟后端记录下的一次成功注入
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MockEvent {
+    Click(MouseButton, Option<ClickPoint>),
+    Button(MouseButton, bool),
+    Move(ClickPoint),
+}
+
+/// 测试用的模拟后端，不产生真实输入
+#[cfg(any(test, feature = "test-util"))]
+pub struct MockBackend {
+    pub fail: std::sync::atomic::AtomicBool,
+    /// 模拟输入队列已满：每次点击的最后一个事件（松开）被丢弃
+    pub drop_events: std::sync::atomic::AtomicBool,
+    pub clicks: std::sync::atomic::AtomicU64,
+    /// 为 true 时点击停在注入中，直到改回 false，模拟卡住的 SendInput
+    pub blocking: std::sync::atomic::AtomicBool,
+    /// 正停在注入中的点击数
+    pub blocked: std::sync::atomic::AtomicU64,
+    /// 当前处于按下状态的按键数（按下 +1，松开 -1）
+    pub held: std::sync::atomic::AtomicI32,
+    pub moves: std::sync::atomic::AtomicU64,
+    pub scrolls: std::sync::atomic::AtomicU64,
+    /// 按时间顺序记录的点击、按键和移动
+    pub events: std::sync::Mutex<Vec<(std::time::Instant, MockEvent)>>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl MockBackend {
+    pub fn new() -> Self {
+        Self {
+            fail: std::sync::atomic::AtomicBool::new(false),
+            drop_events: std::sync::atomic::AtomicBool::new(false),
+            clicks: std::sync::atomic::AtomicU64::new(0),
+            blocking: std::sync::atomic::AtomicBool::new(false),
+            blocked: std::sync::atomic::AtomicU64::new(0),
+            held: std::sync::atomic::AtomicI32::new(0),
+            moves: std::sync::atomic::AtomicU64::new(0),
+            scrolls: std::sync::atomic::AtomicU64::new(0),
+            events: std::sync::Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn failing() -> Self {
+        let backend = Self::new();
+        backend.fail.store(true, std::sync::atomic::Ordering::Relaxed);
+        backend
+    }
+
+    /// 已记录的事件（不含时间）
+    pub fn events(&self) -> Vec<MockEvent> {
+        self.events.lock().unwrap().iter().map(|(_, event)| *event).collect()
+    }
+
+    fn record(&self, event: MockEvent) {
+        self.events.lock().unwrap().push((std::time::Instant::now(), event));
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl InputBackend for MockBackend {
+    fn click(&self, button: MouseButton, position: Option<ClickPoint>) -> u32 {
+        use std::sync::atomic::Ordering;
+
+        if self.blocking.load(Ordering::Relaxed) {
+            self.blocked.fetch_add(1, Ordering::Relaxed);
+            while self.blocking.load(Ordering::Relaxed) {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            self.blocked.fetch_sub(1, Ordering::Relaxed);
+        }
+        if self.fail.load(Ordering::Relaxed) {
+            return 0;
+        }
+        if self.drop_events.load(Ordering::Relaxed) {
+            // 移动和按下已插入，按键停留在按下状态
+            self.held.fetch_add(1, Ordering::Relaxed);
+            self.record(MockEvent::Button(button, true));
+            return expected_click_events(position) - 1;
+        }
+        self.clicks.fetch_add(1, Ordering::Relaxed);
+        self.record(MockEvent::Click(button, position));
+        expected_click_events(position)
+    }
+
+    fn button(&self, button: MouseButton, down: bool) -> bool {
+        use std::sync::atomic::Ordering;
+
+        if self.fail.load(Ordering::Relaxed) {
+            return false;
+        }
+        self.held.fetch_add(if down { 1 } else { -1 }, Ordering::Relaxed);
+        self.record(MockEvent::Button(button, down));
+        true
+    }
+
+    fn key_press(&self, _vk: u16) -> u32 {
+        if self.fail.load(std::sync::atomic::Ordering::Relaxed) {
+            0
+        } else {
+            2
+        }
+    }
+
+    fn move_to(&self, x: i32, y: i32) -> bool {
+        use std::sync::atomic::Ordering;
+
+        if self.fail.load(Ordering::Relaxed) {
+            return false;
+        }
+        self.moves.fetch_add(1, Ordering::Relaxed);
+        self.record(MockEvent::Move(ClickPoint { x, y }));
+        true
+    }
+
+    fn scroll(&self, _direction: ScrollDirection, _positive: bool) -> bool {
+        use std::sync::atomic::Ordering;
+
+        if self.fail.load(Ordering::Relaxed) {
+            return false;
+        }
+        self.scrolls.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+}
@@ -0,0 +1,62 @@
+use crate::input_backend::InputBackend;
+use std::sync::Arc;
+
+/// 键盘事件发送器，按键名称与热键配置中的名称一致
+pub struct KeyboardController {
+    backend: Arc<dyn InputBackend>,
+}
+
+impl KeyboardController {
+    pub fn new(backend: Arc<dyn InputBackend>) -> Self {
+        Self { backend }
+    }
+
+    /// 按下并松开指定按键，返回是否注入成功
+    pub fn press_key(&self, key: &str) -> bool {
+        match Self::virtual_key_code(key) {
+            Some(vk) => self.backend.key_press(vk) >= 2,
+            None => false,
+        }
+    }
+
+    /// 将按键名称转换为 Windows 虚拟键码
+    pub fn virtual_key_code(key: &str) -> Option<u16> {
+        let code = match key {
+            "F1" => 0x70,
+            "F2" => 0x71,
+            "F3" => 0x72,
+            "F4" => 0x73,
+            "F5" => 0x74,
+            "F6" => 0x75,
+            "F7" => 0x76,
+            "F8" => 0x77,
+            "F9" => 0x78,
+            "F10" => 0x79,
+            "F11" => 0x7A,
+            "F12" => 0x7B,
+            "Space" => 0x20,
+            "Enter" => 0x0D,
+            "Esc" => 0x1B,
+            "Tab" => 0x09,
+            "Home" => 0x24,
+            "End" => 0x23,
+            "PageUp" => 0x21,
+            "PageDown" => 0x22,
+            "Insert" => 0x2D,
+            "Delete" => 0x2E,
+            "CapsLock" => 0x14,
+            "NumLock" => 0x90,
+            "ScrollLock" => 0x91,
+            key if key.len() == 1 => {
+                let ch = key.chars().next()?.to_ascii_uppercase();
+                if ch.is_ascii_alphanumeric() {
+                    ch as u16
+                } else {
+                    return None;
+                }
+            }
+            _ => return None,
+        };
+        Some(code)
+    }
+}
@@ -0,0 +1,46 @@
+//! Rust-AC 连点引擎
+//!
+//! 可执行程序 `rust-ac` 只是基于本库的图形界面。嵌入到其他工具时可关闭默认的
+//! `gui` 特性，避免引入 eframe 与托盘依赖：
+//!
+//! ```toml
+//! rust-ac = { package = "Rust-AC", version = "0.1", default-features = false }
+//! ```
+//!
+//! 最简单的用法：
+//!
+//! ```no_run
+//! use rust_ac::{ClickSettings, MouseButton, MouseController};
+//!
+//! let mut controller = MouseController::new();
+//! controller.start_clicking(ClickSettings::new(MouseButton::Left, 100));
+//! std::thread::sleep(std::time::Duration::from_secs(1));
+//! controller.stop_clicking();
+//! println!("共点击 {} 次", controller.get_click_count());
+//! ```
+
+pub mod benchmark;
+pub mod changelog;
+pub mod click_hook;
+pub mod command_file;
+pub mod config;
+pub mod hotkey_manager;
+pub mod input_backend;
+pub mod keyboard_controller;
+pub mod logging;
+pub mod monitor;
+pub mod mouse_controller;
+pub mod overlay;
+pub mod target_window;
+
+/// 配置文件结构及点击参数类型
+pub use config::{
+    ActionStep, AppConfig, BoundAction, ClickPoint, HotkeyBinding, HotkeyConfig, HotkeyPreset, IntervalMode, LimitAction,
+    MouseButton, PointOrder, QuietHours, RunMode, ScrollDirection, TimeOfDay,
+};
+/// 全局热键注册与事件检测
+pub use hotkey_manager::{HotkeyAction, HotkeyManager};
+/// 输入注入后端，实现 `InputBackend` 可替换真实的 SendInput
+pub use input_backend::{DryRunBackend, InputBackend, SendInputBackend};
+/// 连点引擎：`MouseController` 负责点击线程的启动、停止与统计
+pub use mouse_controller::{ClickSettings, MouseController};
@@ -0,0 +1,142 @@
+use crate::config::AppConfig;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use windows::Win32::System::SystemInformation::GetLocalTime;
+
+/// 日志文件超过该大小时轮换
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+
+/// 启动后首次写入及之后每写入这么多行检查一次文件大小
+const ROTATE_CHECK_INTERVAL: u32 = 100;
+
+/// 本次运行已写入的日志行数
+static WRITES: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+
+    /// 从日志行中解析级别，无法识别时视为 Info
+    pub fn of_line(line: &str) -> Self {
+        if line.contains("[ERROR]") {
+            LogLevel::Error
+        } else if line.contains("[WARN]") {
+            LogLevel::Warn
+        } else {
+            LogLevel::Info
+        }
+    }
+}
+
+/// 日志文件路径（位于配置目录）
+pub fn log_path() -> Result<PathBuf, String> {
+    Ok(AppConfig::get_config_dir()?.join("rust-ac.log"))
+}
+
+/// 输出到控制台并追加到日志文件，写文件失败时只输出到控制台
+pub fn log(level: LogLevel, message: &str) {
+    let time = unsafe { GetLocalTime() };
+    let line = format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} [{}] {}",
+        time.wYear, time.wMonth, time.wDay, time.wHour, time.wMinute, time.wSecond,
+        level.as_str(),
+        message
+    );
+
+    if level == LogLevel::Info {
+        println!("{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+
+    let Ok(path) = log_path() else {
+        return;
+    };
+    if WRITES.fetch_add(1, Ordering::Relaxed).is_multiple_of(ROTATE_CHECK_INTERVAL) {
+        rotate_if_large(&path);
+    }
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+fn rotate_if_large(path: &PathBuf) {
+    if fs::metadata(path).is_ok_and(|meta| meta.len() > MAX_LOG_SIZE) {
+        let _ = fs::rename(path, path.with_extension("log.old"));
+    }
+}
+
+/// 读取日志文件最后 count 行
+pub fn read_tail(count: usize) -> Result<Vec<String>, String> {
+    let path = log_path()?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("读取日志文件失败: {}", e)),
+    };
+    Ok(tail_lines(&content, count))
+}
+
+/// 文本的最后 count 行
+fn tail_lines(content: &str, count: usize) -> Vec<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.len().saturating_sub(count);
+    lines[start..].iter().map(|line| line.to_string()).collect()
+}
+
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::LogLevel::Info, &format!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::LogLevel::Warn, &format!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::LogLevel::Error, &format!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_level_from_line() {
+        assert_eq!(LogLevel::of_line("2024-01-01 12:00:00 [ERROR] 热键注册失败"), LogLevel::Error);
+        assert_eq!(LogLevel::of_line("2024-01-01 12:00:00 [WARN] 配置加载失败"), LogLevel::Warn);
+        assert_eq!(LogLevel::of_line("2024-01-01 12:00:00 [INFO] 配置加载成功"), LogLevel::Info);
+        assert_eq!(LogLevel::of_line("无法识别的行"), LogLevel::Info);
+    }
+
+    #[test]
+    fn keeps_only_the_last_lines() {
+        let content = "第一行\n第二行\n第三行\n";
+        assert_eq!(tail_lines(content, 2), ["第二行", "第三行"]);
+        assert_eq!(tail_lines(content, 10).len(), 3);
+        assert!(tail_lines("", 5).is_empty());
+        assert!(tail_lines(content, 0).is_empty());
+    }
+}
@@ -1,17 +1,34 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod app;
-mod config;
-mod hotkey_manager;
-mod mouse_controller;
-
+mod tray_manager;
 
 use app::AutoClickerApp;
 use eframe::egui;
+use rust_ac::config;
 
 fn main() -> Result<(), eframe::Error> {
     env_logger::init();
 
+    // --dump-default-config [路径]：导出默认配置后直接退出，不启动界面
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|arg| arg == "--dump-default-config") {
+        let path = args
+            .get(pos + 1)
+            .map(String::as_str)
+            .unwrap_or("config.example.json");
+        match config::AppConfig::dump_default(std::path::Path::new(path)) {
+            Ok(()) => {
+                println!("默认配置已写入: {}", path);
+                return Ok(());
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
     let icon = load_icon();
 
     let options = eframe::NativeOptions {
@@ -33,7 +50,8 @@ fn main() -> Result<(), eframe::Error> {
     )
 }
 
-fn setup_fonts(ctx: &egui::Context) {
+/// 加载系统中文字体并应用，返回成功加载的字体路径
+pub(crate) fn setup_fonts(ctx: &egui::Context) -> Option<&'static str> {
     use std::fs;
 
     let mut fonts = egui::FontDefinitions::default();
@@ -45,6 +63,7 @@ fn setup_fonts(ctx: &egui::Context) {
         "C:/Windows/Fonts/simhei.ttf",   // 黑体
     ];
 
+    let mut loaded_path = None;
     for (i, font_path) in font_paths.iter().enumerate() {
         if let Ok(font_data) = fs::read(font_path) {
             let font_name = format!("chinese_font_{}", i);
@@ -65,11 +84,18 @@ fn setup_fonts(ctx: &egui::Context) {
                 .or_default()
                 .insert(0, font_name);
 
+            rust_ac::log_info!("已加载字体: {}", font_path);
+            loaded_path = Some(*font_path);
             break; // 找到第一个可用字体就停止
         }
     }
 
+    if loaded_path.is_none() {
+        rust_ac::log_warn!("未找到可用的中文字体，使用默认字体");
+    }
+
     ctx.set_fonts(fonts);
+    loaded_path
 }
 
 fn load_icon() -> egui::IconData {
@@ -0,0 +1,64 @@
+use windows::Win32::Foundation::{BOOL, HWND, LPARAM, RECT, TRUE};
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetDC, GetDeviceCaps, ReleaseDC, HDC, HMONITOR, VREFRESH,
+};
+
+/// 枚举所有显示器的屏幕区域
+pub fn monitor_rects() -> Vec<RECT> {
+    unsafe extern "system" fn collect(
+        _monitor: HMONITOR,
+        _hdc: HDC,
+        rect: *mut RECT,
+        data: LPARAM,
+    ) -> BOOL {
+        let rects = unsafe { &mut *(data.0 as *mut Vec<RECT>) };
+        if let Some(rect) = unsafe { rect.as_ref() } {
+            rects.push(*rect);
+        }
+        TRUE
+    }
+
+    let mut rects: Vec<RECT> = Vec::new();
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            HDC::default(),
+            None,
+            Some(collect),
+            LPARAM(&mut rects as *mut Vec<RECT> as isize),
+        );
+    }
+    rects
+}
+
+/// 当前显示器布局的签名，用于区分不同的显示器组合
+///
+/// 格式如 `0,0,1920x1080;1920,0,2560x1440`，按位置排序保证稳定
+pub fn layout_signature() -> String {
+    let mut rects = monitor_rects();
+    rects.sort_by_key(|r| (r.left, r.top));
+    rects
+        .iter()
+        .map(|r| format!("{},{},{}x{}", r.left, r.top, r.right - r.left, r.bottom - r.top))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// 签名中包含的显示器数量
+pub fn monitor_count(signature: &str) -> usize {
+    signature.split(';').filter(|s| !s.is_empty()).count()
+}
+
+/// 主显示器的刷新率（Hz），无法获取时返回 None
+pub fn refresh_rate() -> Option<u32> {
+    let hz = unsafe {
+        let hdc = GetDC(HWND::default());
+        if hdc.is_invalid() {
+            return None;
+        }
+        let hz = GetDeviceCaps(hdc, VREFRESH);
+        ReleaseDC(HWND::default(), hdc);
+        hz
+    };
+    // 0 和 1 表示使用硬件默认值，无法得知具体刷新率
+    if hz > 1 { Some(hz as u32) } else { None }
+}
@@ -1,67 +1,464 @@
-use crate::config::MouseButton;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use crate::config::{ActionStep, ClickPoint, LimitAction, MouseButton, PointOrder, QuietHours, RunMode, TimeOfDay};
+use crate::input_backend::{
+    expected_click_events, DryRunBackend, InputBackend, SendInputBackend, CLICK_EVENT_COUNT,
+};
+use crate::keyboard_controller::KeyboardController;
+use crate::target_window::TargetWindow;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
-use windows::Win32::UI::Input::KeyboardAndMouse::{
-    mouse_event, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP, MOUSEEVENTF_RIGHTDOWN,
-    MOUSEEVENTF_RIGHTUP,
-};
+
+use windows::Win32::Foundation::POINT;
+use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
 
 #[cfg(windows)]
 use windows::Win32::System::Threading::{
     GetCurrentThread, SetThreadPriority, THREAD_PRIORITY_BELOW_NORMAL,
 };
 
+/// 连续注入失败达到该次数后自动停止
+const MAX_CONSECUTIVE_FAILURES: u32 = 10;
+
+/// 停止时等待工作线程结束的最长时间，超时后放弃等待，避免界面卡住
+const STOP_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// 系统丢弃事件时追加间隔的下限和上限，丢弃时翻倍，成功后逐渐回落
+const MIN_BACKOFF: Duration = Duration::from_millis(1);
+const MAX_BACKOFF: Duration = Duration::from_millis(100);
+
+/// 一次连点的参数
+#[derive(Debug, Clone)]
+pub struct ClickSettings {
+    pub button: MouseButton,
+    pub interval_ms: u64,
+    /// 依次轮流点击的坐标，为空时在当前光标位置点击
+    pub points: Vec<ClickPoint>,
+    /// 多个坐标的轮流顺序
+    pub point_order: PointOrder,
+    /// 设置后 points 为相对该标题窗口客户区的坐标
+    pub target_window: Option<String>,
+    /// 点击次数上限，0 表示不限制
+    pub click_limit: u64,
+    pub on_limit: LimitAction,
+    /// 在当前光标位置点击时附带一次绝对移动事件，兼容只认带移动的点击的游戏
+    pub reassert_position: bool,
+    /// 空跑时改用不注入的后端，单次点击时点击一次后停止
+    pub mode: RunMode,
+    /// 第一次点击前的随机延迟范围（毫秒），延迟期间停止则不会点击
+    pub start_jitter_ms: (u64, u64),
+    /// 点击节奏：每个间隔依次取一格，true 点击、false 跳过，为空时每格都点击
+    pub pattern: Vec<bool>,
+    /// 固定坐标点击时在每个坐标周围随机偏移的最大像素数
+    pub position_jitter: u32,
+    /// 每次点击按住的随机时长范围（毫秒），为 0 时按下后立即松开
+    pub hold_ms: (u64, u64),
+    /// 每次间隔在基础间隔上下随机浮动的百分比
+    pub interval_jitter_pct: u32,
+    /// 点击固定坐标前沿曲线平滑移动光标的速度（像素/秒），0 表示直接跳到坐标
+    pub glide_speed: u32,
+}
+
+impl ClickSettings {
+    pub fn new(button: MouseButton, interval_ms: u64) -> Self {
+        Self {
+            button,
+            interval_ms,
+            points: Vec::new(),
+            point_order: PointOrder::Sequential,
+            target_window: None,
+            click_limit: 0,
+            on_limit: LimitAction::Stop,
+            reassert_position: false,
+            mode: RunMode::Real,
+            start_jitter_ms: (0, 0),
+            pattern: Vec::new(),
+            position_jitter: 0,
+            hold_ms: (0, 0),
+            interval_jitter_pct: 0,
+            glide_speed: 0,
+        }
+    }
+}
+
+/// 拖拽参数：按下后沿路径移动再松开，循环执行
+#[derive(Debug, Clone)]
+pub struct DragSettings {
+    pub button: MouseButton,
+    /// 拖拽经过的屏幕坐标，至少两个点
+    pub path: Vec<ClickPoint>,
+    /// 移动速度（像素/秒）
+    pub speed: u32,
+    /// 两次拖拽之间的间隔（毫秒）
+    pub pause_ms: u64,
+}
+
+/// 拖拽移动时每步的时间
+const DRAG_STEP: Duration = Duration::from_millis(5);
+
+/// 拖拽中保持按下的按键，离开作用域时保证松开
+struct HeldButton<'a> {
+    backend: &'a dyn InputBackend,
+    button: MouseButton,
+}
+
+impl<'a> HeldButton<'a> {
+    fn press(backend: &'a dyn InputBackend, button: MouseButton) -> Option<Self> {
+        backend.button(button, true).then_some(Self { backend, button })
+    }
+}
+
+impl Drop for HeldButton<'_> {
+    fn drop(&mut self) {
+        if !self.backend.button(self.button, false) {
+            crate::log_warn!("拖拽结束时松开按键失败");
+        }
+    }
+}
+
+/// 多个工作线程共享的坐标序号，按设置的顺序给出下一个坐标
+struct PointCursor {
+    order: PointOrder,
+    len: usize,
+    next: AtomicUsize,
+    /// 随机顺序下最近两轮的轮次及坐标排列，按轮次奇偶存放，
+    /// 新一轮开始后，上一轮尚未取完坐标的线程仍使用它那一轮的排列
+    shuffled: Mutex<[(usize, Vec<usize>); 2]>,
+}
+
+impl PointCursor {
+    fn new(order: PointOrder, len: usize) -> Self {
+        Self {
+            order,
+            len,
+            next: AtomicUsize::new(0),
+            shuffled: Mutex::new([(0, shuffled_indices(len)), (1, shuffled_indices(len))]),
+        }
+    }
+
+    fn next(&self) -> usize {
+        self.index_for(self.next.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// 第 n 次取号对应的坐标序号
+    fn index_for(&self, n: usize) -> usize {
+        let slot = n % self.len;
+        match self.order {
+            PointOrder::Sequential => slot,
+            PointOrder::Reverse => self.len - 1 - slot,
+            PointOrder::Random => {
+                let Ok(mut shuffled) = self.shuffled.lock() else {
+                    return slot;
+                };
+                // 进入新一轮时重新打乱，线程先后取号不会让同一轮重复打乱
+                let cycle = n / self.len;
+                let (stored_cycle, order) = &mut shuffled[cycle % 2];
+                if cycle > *stored_cycle {
+                    *stored_cycle = cycle;
+                    *order = shuffled_indices(self.len);
+                }
+                order[slot]
+            }
+        }
+    }
+}
+
+/// 二次贝塞尔曲线上均匀取 steps 个点（不含起点），最后一点恰好是终点
+fn bezier_path(from: ClickPoint, control: (f64, f64), to: ClickPoint, steps: u32) -> Vec<ClickPoint> {
+    (1..=steps)
+        .map(|i| {
+            let t = i as f64 / steps as f64;
+            let u = 1.0 - t;
+            let x = u * u * from.x as f64 + 2.0 * u * t * control.0 + t * t * to.x as f64;
+            let y = u * u * from.y as f64 + 2.0 * u * t * control.1 + t * t * to.y as f64;
+            ClickPoint { x: x.round() as i32, y: y.round() as i32 }
+        })
+        .collect()
+}
+
+/// 0..len 的随机排列
+fn shuffled_indices(len: usize) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..len).collect();
+    for i in (1..len).rev() {
+        indices.swap(i, random_between(0, i as u64) as usize);
+    }
+    indices
+}
+
+/// 在 [min, max] 内取一个随机值，max 不大于 min 时返回 min
+fn random_between(min: u64, max: u64) -> u64 {
+    use std::hash::BuildHasher;
+
+    if max <= min {
+        return min;
+    }
+    // 标准库的 RandomState 每次创建都使用不同的随机密钥，足够用于打散延迟
+    let random = std::collections::hash_map::RandomState::new().hash_one(Instant::now());
+    // 区间覆盖整个 u64 时 max - min + 1 会溢出，直接使用随机值
+    match (max - min).checked_add(1) {
+        Some(span) => min + random % span,
+        None => random,
+    }
+}
+
+/// 在坐标周围 ±max 像素的方形范围内随机偏移
+fn jittered_point(point: ClickPoint, max: u32) -> ClickPoint {
+    let offset = || random_between(0, 2 * max as u64) as i32 - max as i32;
+    ClickPoint { x: point.x + offset(), y: point.y + offset() }
+}
+
+/// 在基础间隔上下随机浮动 pct%
+fn jittered_interval(interval: Duration, pct: u32) -> Duration {
+    if pct == 0 {
+        return interval;
+    }
+    let base = interval.as_micros() as u64;
+    let spread = base * pct.min(100) as u64 / 100;
+    Duration::from_micros(base - spread + random_between(0, 2 * spread))
+}
+
+/// 单次注入耗时的滑动平均，用于提前触发以抵消注入本身的延迟
+#[derive(Debug, Default, Clone, Copy)]
+struct InjectionCost(Duration);
+
+impl InjectionCost {
+    /// 记录一次注入耗时，平均值最多为一个间隔
+    fn record(&mut self, sample: Duration, interval: Duration) {
+        self.0 = ((self.0 * 7 + sample) / 8).min(interval);
+    }
+
+    /// 为了在 next_click 时完成注入而提前开始的时刻
+    fn fire_at(&self, next_click: Instant) -> Instant {
+        next_click.checked_sub(self.0).unwrap_or(next_click)
+    }
+}
+
+/// 节奏测试的点击节奏：连点三下后停顿两格
+const RHYTHM_TEST_PATTERN: [bool; 5] = [true, true, true, false, false];
+
+/// 节奏测试点击的总次数（四组）
+const RHYTHM_TEST_CLICKS: u64 = 12;
+
+/// 运行中检查禁止时段的间隔
+const QUIET_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 工作线程中定期检查是否进入禁止时段，避免每次点击都读取系统时间
+struct QuietWatch {
+    hours: Option<QuietHours>,
+    next_check: Instant,
+}
+
+impl QuietWatch {
+    fn new(hours: Option<QuietHours>) -> Self {
+        Self { hours, next_check: Instant::now() + QUIET_CHECK_INTERVAL }
+    }
+
+    fn entered(&mut self) -> bool {
+        let Some(hours) = self.hours else {
+            return false;
+        };
+        let now = Instant::now();
+        if now < self.next_check {
+            return false;
+        }
+        self.next_check = now + QUIET_CHECK_INTERVAL;
+        hours.contains(TimeOfDay::now())
+    }
+}
+
+/// 一次运行中工作线程与调用方共享的状态，每次开始时新建
+///
+/// 停止时放弃等待的线程只持有它那一次运行的状态，结束前不会影响之后的运行
+#[derive(Default)]
+struct RunState {
+    is_running: AtomicBool,
+    click_count: AtomicU64,
+    injection_failed: AtomicBool,
+    throttled: AtomicBool,
+    limit_reached: AtomicBool,
+    quiet_stopped: AtomicBool,
+    paused: AtomicBool,
+}
+
+impl RunState {
+    fn started() -> Self {
+        Self {
+            is_running: AtomicBool::new(true),
+            ..Self::default()
+        }
+    }
+
+    /// 已停止的副本，保留结果供界面显示
+    fn stopped_copy(&self) -> Self {
+        let flag = |value: &AtomicBool| AtomicBool::new(value.load(Ordering::Relaxed));
+        Self {
+            is_running: AtomicBool::new(false),
+            click_count: AtomicU64::new(self.click_count.load(Ordering::Relaxed)),
+            injection_failed: flag(&self.injection_failed),
+            throttled: flag(&self.throttled),
+            limit_reached: flag(&self.limit_reached),
+            quiet_stopped: flag(&self.quiet_stopped),
+            paused: AtomicBool::new(false),
+        }
+    }
+}
+
 pub struct MouseController {
-    is_running: Arc<AtomicBool>,
-    click_count: Arc<AtomicU64>,
+    backend: Arc<dyn InputBackend>,
+    run: Arc<RunState>,
+    quiet_hours: Option<QuietHours>,
     handles: Vec<thread::JoinHandle<()>>,
     start_time: Option<Instant>,
+    paused_since: Option<Instant>,
+    /// 本次运行中已结束的暂停累计时长
+    paused_total: Duration,
+    pause_freezes_stats: bool,
 }
 
 impl MouseController {
     pub fn new() -> Self {
+        Self::with_backend(Arc::new(SendInputBackend))
+    }
+
+    pub fn with_backend(backend: Arc<dyn InputBackend>) -> Self {
         Self {
-            is_running: Arc::new(AtomicBool::new(false)),
-            click_count: Arc::new(AtomicU64::new(0)),
+            backend,
+            run: Arc::new(RunState::default()),
+            quiet_hours: None,
             handles: Vec::new(),
             start_time: None,
+            paused_since: None,
+            paused_total: Duration::ZERO,
+            pause_freezes_stats: true,
         }
     }
 
-    pub fn start_clicking(&mut self, button: MouseButton, interval_ms: u64) {
-        if self.is_running.load(Ordering::Relaxed) {
-            return;
+    /// 设置禁止连点的时间段，对之后开始的连点、宏和拖拽生效
+    pub fn set_quiet_hours(&mut self, hours: Option<QuietHours>) {
+        self.quiet_hours = hours;
+    }
+
+    /// 上次运行是否因处于禁止时段而被拒绝或停止
+    pub fn stopped_by_quiet_hours(&self) -> bool {
+        self.run.quiet_stopped.load(Ordering::Relaxed)
+    }
+
+    /// 暂停时是否停止计时，开启时运行时间和 CPS 不包含暂停的时长
+    pub fn set_pause_freezes_stats(&mut self, freeze: bool) {
+        self.pause_freezes_stats = freeze;
+    }
+
+    /// 暂停连点，工作线程保持运行但不再点击
+    pub fn pause(&mut self) {
+        if self.is_running() && !self.run.paused.swap(true, Ordering::Relaxed) {
+            self.paused_since = Some(Instant::now());
+        }
+    }
+
+    /// 从暂停处继续连点
+    pub fn resume(&mut self) {
+        self.run.paused.store(false, Ordering::Relaxed);
+        if let Some(since) = self.paused_since.take() {
+            self.paused_total += since.elapsed();
         }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.run.paused.load(Ordering::Relaxed)
+    }
 
-        self.is_running.store(true, Ordering::Relaxed);
-        self.click_count.store(0, Ordering::Relaxed);
+    /// 每次开始运行时新建运行状态，并重置计时和暂停状态
+    fn begin_run(&mut self) {
+        self.run = Arc::new(RunState::started());
         self.start_time = Some(Instant::now());
+        self.paused_since = None;
+        self.paused_total = Duration::ZERO;
+    }
 
-        // 防止除零
-        if interval_ms == 0 {
+    /// 开始前检查禁止时段，处于时段内时记录原因并返回 false
+    fn allowed_now(&self) -> bool {
+        let quiet = self.quiet_hours.is_some_and(|hours| hours.contains(TimeOfDay::now()));
+        self.run.quiet_stopped.store(quiet, Ordering::Relaxed);
+        !quiet
+    }
+
+    /// 在独立线程中开始连点，立即返回
+    ///
+    /// 点击线程只通过原子变量与调用方通信，界面重绘、拖动窗口等都不会阻塞点击节奏
+    pub fn start_clicking(&mut self, settings: ClickSettings) {
+        if self.run.is_running.load(Ordering::Relaxed) || !self.allowed_now() {
             return;
         }
 
-        // 根据 CPS 决定线程数量
-        let thread_count = if interval_ms >= 20 {
-            1 // <= 50 CPS: 单线程足够
-        } else if interval_ms >= 5 {
-            2 // 50-200 CPS: 2 线程
-        } else if interval_ms >= 2 {
-            4 // 200-500 CPS: 4 线程
-        } else {
-            8 // > 500 CPS: 8 线程
+        let ClickSettings {
+            button,
+            interval_ms,
+            points,
+            point_order,
+            target_window,
+            mut click_limit,
+            mut on_limit,
+            reassert_position,
+            mode,
+            start_jitter_ms: (jitter_min, jitter_max),
+            mut pattern,
+            position_jitter,
+            hold_ms,
+            mut interval_jitter_pct,
+            glide_speed,
+        } = settings;
+        let backend: Arc<dyn InputBackend> = match mode {
+            RunMode::DryRun => Arc::new(DryRunBackend::new()),
+            _ => Arc::clone(&self.backend),
         };
+        match mode {
+            RunMode::SingleShot => {
+                click_limit = 1;
+                on_limit = LimitAction::Stop;
+            }
+            RunMode::RhythmTest => {
+                // 间隔浮动会打乱节奏，测试时关闭
+                pattern = RHYTHM_TEST_PATTERN.to_vec();
+                interval_jitter_pct = 0;
+                click_limit = RHYTHM_TEST_CLICKS;
+                on_limit = LimitAction::Stop;
+            }
+            _ => {}
+        }
+        let points = Arc::new(points);
+        let target_window = Arc::new(target_window.map(TargetWindow::new));
+        let next_point = Arc::new(PointCursor::new(point_order, points.len()));
+        // 全部跳过的节奏等同于不点击，按每格都点击处理
+        let pattern = Arc::new(if pattern.contains(&true) { pattern } else { Vec::new() });
+        let next_slot = Arc::new(AtomicUsize::new(0));
+        let restart_on_limit = on_limit == LimitAction::Restart;
+        // 已预留的点击名额，保证多线程下点击次数不会超过上限
+        let reserved = Arc::new(AtomicU64::new(0));
 
-        let is_running = Arc::clone(&self.is_running);
-        let click_count = Arc::clone(&self.click_count);
+        self.begin_run();
+
+        // 防止除零
+        if interval_ms == 0 {
+            return;
+        }
+
+        let (thread_count, _) = Self::thread_plan(interval_ms);
+        // 多个线程同时移动光标会互相干扰，只在单线程时平滑移动
+        let glide_speed = if thread_count == 1 { glide_speed } else { 0 };
+        let first_click = Instant::now() + Duration::from_millis(random_between(jitter_min, jitter_max));
 
         // 创建多个点击线程
         for thread_id in 0..thread_count {
-            let is_running = Arc::clone(&is_running);
-            let click_count = Arc::clone(&click_count);
+            let run = Arc::clone(&self.run);
+            let backend = Arc::clone(&backend);
+            let points = Arc::clone(&points);
+            let next_point = Arc::clone(&next_point);
+            let pattern = Arc::clone(&pattern);
+            let next_slot = Arc::clone(&next_slot);
+            let target_window = Arc::clone(&target_window);
+            let reserved = Arc::clone(&reserved);
+            // 只由第一个线程检查禁止时段
+            let mut quiet = QuietWatch::new(self.quiet_hours.filter(|_| thread_id == 0));
             
             let handle = thread::spawn(move || {
                 // 设置线程优先级
@@ -72,15 +469,138 @@ impl MouseController {
                 
                 let interval = Duration::from_millis(interval_ms * thread_count as u64);
                 let offset = Duration::from_millis(interval_ms * thread_id as u64);
-                let mut next_click = Instant::now() + offset;
+                let mut next_click = first_click + offset;
+                let mut consecutive_failures = 0;
+                let mut injection_cost = InjectionCost::default();
+                // 系统输入队列丢弃事件时追加的间隔
+                let mut backoff = Duration::ZERO;
                 
-                while is_running.load(Ordering::Relaxed) {
+                while run.is_running.load(Ordering::Relaxed) {
+                    if quiet.entered() {
+                        run.quiet_stopped.store(true, Ordering::Relaxed);
+                        run.is_running.store(false, Ordering::Relaxed);
+                        break;
+                    }
+
+                    if run.paused.load(Ordering::Relaxed) {
+                        // 继续后按线程偏移重新排布，避免多个线程同时补点
+                        Self::sleep_while_running(&run.is_running, Duration::from_millis(10));
+                        next_click = Instant::now() + offset;
+                        continue;
+                    }
+
                     let now = Instant::now();
+                    let fire_at = injection_cost.fire_at(next_click);
                     
-                    if now >= next_click {
-                        Self::simulate_click(button);
-                        click_count.fetch_add(1, Ordering::Relaxed);
-                        next_click += interval;
+                    if now >= fire_at {
+                        // 多个线程共享节奏序号，跳过的格子不点击也不占用坐标
+                        if !pattern.is_empty() && !pattern[next_slot.fetch_add(1, Ordering::Relaxed) % pattern.len()] {
+                            next_click += interval;
+                            if next_click < now {
+                                next_click = now + interval;
+                            }
+                            continue;
+                        }
+
+                        // 多个线程共享坐标序号，保证按顺序轮流点击各坐标
+                        let position = if points.is_empty() {
+                            // 获取失败时退回为纯按键事件
+                            if reassert_position { Self::cursor_position() } else { None }
+                        } else {
+                            Some(points[next_point.next()])
+                        };
+
+                        // 每次点击前按目标窗口当前位置换算，窗口不可用时跳过本次点击
+                        let position = match (position, target_window.as_ref()) {
+                            (Some(point), Some(window)) => match window.client_to_screen(point) {
+                                Some(screen) => Some(screen),
+                                None => {
+                                    next_click = now + interval;
+                                    continue;
+                                }
+                            },
+                            (position, _) => position,
+                        };
+                        // 只对固定坐标做随机偏移，当前光标位置保持不动
+                        let position = match position {
+                            Some(point) if position_jitter > 0 && !points.is_empty() => {
+                                Some(jittered_point(point, position_jitter))
+                            }
+                            position => position,
+                        };
+
+                        if glide_speed > 0
+                            && !points.is_empty()
+                            && let (Some(from), Some(to)) = (Self::cursor_position(), position)
+                            && !Self::glide(&*backend, &run.is_running, from, to, glide_speed, Duration::from_millis(interval_ms / 2))
+                        {
+                            // 中途停止时直接退出，移动失败与注入失败一样计数并推迟下次点击
+                            if !run.is_running.load(Ordering::Relaxed) {
+                                continue;
+                            }
+                            consecutive_failures += 1;
+                            if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                                run.injection_failed.store(true, Ordering::Relaxed);
+                                run.is_running.store(false, Ordering::Relaxed);
+                                break;
+                            }
+                            next_click += jittered_interval(interval, interval_jitter_pct) + backoff;
+                            if next_click < now {
+                                next_click = now + interval;
+                            }
+                            continue;
+                        }
+
+                        let limited = click_limit > 0 && !restart_on_limit;
+                        if limited && reserved.fetch_add(1, Ordering::Relaxed) >= click_limit {
+                            // 名额已满，由完成最后一次点击的线程负责停止
+                            reserved.fetch_sub(1, Ordering::Relaxed);
+                            break;
+                        }
+
+                        let (injected, dropped) = if hold_ms.1 > 0 {
+                            // 按住时长属于点击本身，不计入注入耗时补偿
+                            (Self::held_click(&*backend, &run.is_running, button, position, hold_ms, interval_ms), false)
+                        } else {
+                            let inject_start = Instant::now();
+                            let events = Self::click_once(&*backend, button, position);
+                            injection_cost.record(inject_start.elapsed(), interval);
+                            // 只插入了部分事件说明输入队列已满，与完全被拦截区分开
+                            let expected = expected_click_events(position);
+                            (events >= expected, events > 0 && events < expected)
+                        };
+
+                        if dropped {
+                            // 不计入点击次数，加大间隔让系统消化积压的事件
+                            if limited {
+                                reserved.fetch_sub(1, Ordering::Relaxed);
+                            }
+                            consecutive_failures = 0;
+                            backoff = (backoff * 2).clamp(MIN_BACKOFF, MAX_BACKOFF);
+                            run.throttled.store(true, Ordering::Relaxed);
+                        } else if injected {
+                            consecutive_failures = 0;
+                            backoff = backoff * 15 / 16;
+                            // 归零继续时计数保持累加，由界面按上限取余显示本轮进度
+                            let count = run.click_count.fetch_add(1, Ordering::Relaxed) + 1;
+                            if limited && count == click_limit {
+                                run.limit_reached.store(true, Ordering::Relaxed);
+                                run.is_running.store(false, Ordering::Relaxed);
+                                break;
+                            }
+                        } else {
+                            // 事件未能注入，不计入点击次数，归还预留的名额
+                            if limited {
+                                reserved.fetch_sub(1, Ordering::Relaxed);
+                            }
+                            consecutive_failures += 1;
+                            if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                                run.injection_failed.store(true, Ordering::Relaxed);
+                                run.is_running.store(false, Ordering::Relaxed);
+                                break;
+                            }
+                        }
+                        next_click += jittered_interval(interval, interval_jitter_pct) + backoff;
                         
                         // 防止时间漂移
                         if next_click < now {
@@ -89,13 +609,15 @@ impl MouseController {
                     }
                     
                     // 智能睡眠
-                    let time_until_next = next_click.saturating_duration_since(Instant::now());
+                    let fire_at = injection_cost.fire_at(next_click);
+                    let time_until_next = fire_at.saturating_duration_since(Instant::now());
                     if time_until_next > Duration::from_millis(2) {
-                        thread::sleep(time_until_next - Duration::from_millis(1));
+                        // 分段睡眠，长间隔下也能及时响应停止
+                        Self::sleep_while_running(&run.is_running, time_until_next - Duration::from_millis(1));
                     } else if time_until_next > Duration::ZERO {
                         // 短暂忙等待提高精度
                         let spin_until = Instant::now() + time_until_next;
-                        while Instant::now() < spin_until && is_running.load(Ordering::Relaxed) {
+                        while Instant::now() < spin_until && run.is_running.load(Ordering::Relaxed) {
                             std::hint::spin_loop();
                         }
                     }
@@ -106,25 +628,336 @@ impl MouseController {
         }
     }
 
+    /// 根据点击间隔决定工作线程数量，返回线程数和选择原因
+    pub fn thread_plan(interval_ms: u64) -> (usize, &'static str) {
+        if interval_ms >= 20 {
+            (1, "间隔 ≥ 20ms (≤ 50 CPS)，单线程足够")
+        } else if interval_ms >= 5 {
+            (2, "间隔 5-19ms (50-200 CPS)，使用 2 线程")
+        } else if interval_ms >= 2 {
+            (4, "间隔 2-4ms (200-500 CPS)，使用 4 线程")
+        } else {
+            (8, "间隔 < 2ms (> 500 CPS)，使用 8 线程")
+        }
+    }
+
+    /// 当前正在运行的工作线程数
+    pub fn thread_count(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// 在单独线程中循环执行宏步骤，直到停止
+    pub fn start_macro(&mut self, steps: Vec<ActionStep>) {
+        if self.run.is_running.load(Ordering::Relaxed) || steps.is_empty() || !self.allowed_now() {
+            return;
+        }
+
+        self.begin_run();
+
+        let run = Arc::clone(&self.run);
+        let backend = Arc::clone(&self.backend);
+        let mut quiet = QuietWatch::new(self.quiet_hours);
+
+        let handle = thread::spawn(move || {
+            let keyboard = KeyboardController::new(Arc::clone(&backend));
+            let mut consecutive_failures = 0;
+
+            'outer: while run.is_running.load(Ordering::Relaxed) {
+                for step in &steps {
+                    if !run.is_running.load(Ordering::Relaxed) {
+                        break 'outer;
+                    }
+                    if quiet.entered() {
+                        run.quiet_stopped.store(true, Ordering::Relaxed);
+                        run.is_running.store(false, Ordering::Relaxed);
+                        break 'outer;
+                    }
+
+                    let injected = match step {
+                        ActionStep::KeyPress { key } => keyboard.press_key(key),
+                        ActionStep::MouseClick { button } => {
+                            let ok = Self::click_once(&*backend, *button, None) >= CLICK_EVENT_COUNT;
+                            if ok {
+                                run.click_count.fetch_add(1, Ordering::Relaxed);
+                            }
+                            ok
+                        }
+                        ActionStep::Wait { ms } => {
+                            Self::sleep_while_running(&run.is_running, Duration::from_millis(*ms));
+                            true
+                        }
+                        ActionStep::MoveTo { x, y } => backend.move_to(*x, *y),
+                        ActionStep::Scroll { direction, amount } => {
+                            // 逐格发送，每格计为一次事件
+                            let mut ok = true;
+                            for _ in 0..amount.unsigned_abs() {
+                                if !run.is_running.load(Ordering::Relaxed) {
+                                    break;
+                                }
+                                if !backend.scroll(*direction, *amount > 0) {
+                                    ok = false;
+                                    break;
+                                }
+                                run.click_count.fetch_add(1, Ordering::Relaxed);
+                            }
+                            ok
+                        }
+                    };
+
+                    if injected {
+                        consecutive_failures = 0;
+                    } else {
+                        consecutive_failures += 1;
+                        if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                            run.injection_failed.store(true, Ordering::Relaxed);
+                            run.is_running.store(false, Ordering::Relaxed);
+                            break 'outer;
+                        }
+                    }
+                }
+            }
+        });
+
+        self.handles.push(handle);
+    }
+
+    /// 在单独线程中循环执行拖拽，每完成一次拖拽计为一次点击
+    pub fn start_drag(&mut self, settings: DragSettings) {
+        if self.run.is_running.load(Ordering::Relaxed)
+            || settings.path.len() < 2
+            || settings.speed == 0
+            || !self.allowed_now()
+        {
+            return;
+        }
+
+        self.begin_run();
+
+        let run = Arc::clone(&self.run);
+        let backend = Arc::clone(&self.backend);
+        let mut quiet = QuietWatch::new(self.quiet_hours);
+
+        let handle = thread::spawn(move || {
+            let DragSettings { button, path, speed, pause_ms } = settings;
+            let mut consecutive_failures = 0;
+
+            while run.is_running.load(Ordering::Relaxed) {
+                if quiet.entered() {
+                    run.quiet_stopped.store(true, Ordering::Relaxed);
+                    run.is_running.store(false, Ordering::Relaxed);
+                    break;
+                }
+                let completed = Self::drag_once(&*backend, &run.is_running, button, &path, speed);
+                if completed {
+                    consecutive_failures = 0;
+                    run.click_count.fetch_add(1, Ordering::Relaxed);
+                } else if run.is_running.load(Ordering::Relaxed) {
+                    consecutive_failures += 1;
+                    if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                        run.injection_failed.store(true, Ordering::Relaxed);
+                        run.is_running.store(false, Ordering::Relaxed);
+                        break;
+                    }
+                }
+                Self::sleep_while_running(&run.is_running, Duration::from_millis(pause_ms));
+            }
+        });
+
+        self.handles.push(handle);
+    }
+
+    /// 注入一次点击，按下已插入而松开被丢弃时补发松开，避免按键卡在按下状态
+    ///
+    /// 停止时放弃等待的线程从卡住的注入返回后也由这里松开，只松开本次点击的按键
+    fn click_once(backend: &dyn InputBackend, button: MouseButton, position: Option<ClickPoint>) -> u32 {
+        let events = backend.click(button, position);
+        if events + 1 == expected_click_events(position) {
+            backend.button(button, false);
+        }
+        events
+    }
+
+    /// 按下后保持一段随机时长再松开，按住时长不超过基础间隔的一半，避免与下一次点击重叠
+    ///
+    /// 停止时提前松开，不会因按住时长拖慢停止
+    fn held_click(
+        backend: &dyn InputBackend,
+        is_running: &AtomicBool,
+        button: MouseButton,
+        position: Option<ClickPoint>,
+        hold_ms: (u64, u64),
+        interval_ms: u64,
+    ) -> bool {
+        if let Some(point) = position
+            && !backend.move_to(point.x, point.y)
+        {
+            return false;
+        }
+        let hold = random_between(hold_ms.0, hold_ms.1).min(interval_ms / 2);
+        let Some(_held) = HeldButton::press(backend, button) else {
+            return false;
+        };
+        Self::sleep_while_running(is_running, Duration::from_millis(hold));
+        true
+    }
+
+    /// 沿随机弯曲的贝塞尔曲线把光标移到目标点，速度上下浮动 20%，耗时不超过 max_duration
+    ///
+    /// 中途停止或移动失败时返回 false
+    fn glide(
+        backend: &dyn InputBackend,
+        is_running: &AtomicBool,
+        from: ClickPoint,
+        to: ClickPoint,
+        speed: u32,
+        max_duration: Duration,
+    ) -> bool {
+        let (dx, dy) = ((to.x - from.x) as f64, (to.y - from.y) as f64);
+        let distance = dx.hypot(dy);
+        if distance < 2.0 {
+            return true;
+        }
+        let speed = speed as f64 * random_between(80, 120) as f64 / 100.0;
+        let duration = Duration::from_secs_f64(distance / speed).min(max_duration);
+        let steps = (duration.as_secs_f64() / DRAG_STEP.as_secs_f64()).ceil().max(1.0) as u32;
+
+        // 控制点在连线中点的垂直方向上随机偏移，最多为距离的 20%
+        let bend = (random_between(0, 40) as f64 - 20.0) / 100.0;
+        let control = (
+            (from.x + to.x) as f64 / 2.0 - dy * bend,
+            (from.y + to.y) as f64 / 2.0 + dx * bend,
+        );
+
+        for point in bezier_path(from, control, to, steps) {
+            if !backend.move_to(point.x, point.y) {
+                return false;
+            }
+            Self::sleep_while_running(is_running, duration / steps);
+            if !is_running.load(Ordering::Relaxed) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// 执行一次完整拖拽，注入失败或中途停止时返回 false，按键总会被松开
+    fn drag_once(
+        backend: &dyn InputBackend,
+        is_running: &AtomicBool,
+        button: MouseButton,
+        path: &[ClickPoint],
+        speed: u32,
+    ) -> bool {
+        if !backend.move_to(path[0].x, path[0].y) {
+            return false;
+        }
+        let Some(_held) = HeldButton::press(backend, button) else {
+            return false;
+        };
+
+        for segment in path.windows(2) {
+            let (from, to) = (segment[0], segment[1]);
+            let (dx, dy) = ((to.x - from.x) as f64, (to.y - from.y) as f64);
+            let duration = Duration::from_secs_f64(dx.hypot(dy) / speed as f64);
+            let steps = (duration.as_secs_f64() / DRAG_STEP.as_secs_f64()).ceil().max(1.0) as u32;
+
+            for i in 1..=steps {
+                let t = i as f64 / steps as f64;
+                let x = from.x + (dx * t).round() as i32;
+                let y = from.y + (dy * t).round() as i32;
+                if !backend.move_to(x, y) {
+                    return false;
+                }
+                Self::sleep_while_running(is_running, duration / steps);
+                if !is_running.load(Ordering::Relaxed) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// 分段睡眠，停止后最多延迟一个分段即返回
+    fn sleep_while_running(is_running: &AtomicBool, duration: Duration) {
+        const CHUNK: Duration = Duration::from_millis(50);
+
+        let deadline = Instant::now() + duration;
+        while is_running.load(Ordering::Relaxed) {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            thread::sleep(remaining.min(CHUNK));
+        }
+    }
+
+    /// 停止并等待工作线程结束，最多等待 STOP_TIMEOUT
+    ///
+    /// 正在按住或注入中的线程超时后不再等待，它只持有本次运行的状态，
+    /// 不会影响之后的运行，注入返回后由它自己松开按键
     pub fn stop_clicking(&mut self) {
-        self.is_running.store(false, Ordering::Relaxed);
+        self.run.is_running.store(false, Ordering::Relaxed);
+        self.resume();
 
-        // 等待所有线程结束
-        for handle in self.handles.drain(..) {
-            let _ = handle.join();
+        let deadline = Instant::now() + STOP_TIMEOUT;
+        let mut detached = 0;
+        for handle in std::mem::take(&mut self.handles) {
+            while !handle.is_finished() && Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(1));
+            }
+            if handle.is_finished() {
+                let _ = handle.join();
+            } else {
+                detached += 1;
+            }
+        }
+
+        if detached > 0 {
+            crate::log_warn!("{} 个工作线程未能在 {}ms 内结束，已放弃等待", detached, STOP_TIMEOUT.as_millis());
+            // 停止时的结果保留给界面，旧线程之后的改动不再显示
+            self.run = Arc::new(self.run.stopped_copy());
         }
     }
 
+    /// 获取当前光标的屏幕坐标
+    pub fn cursor_position() -> Option<ClickPoint> {
+        let mut point = POINT::default();
+        unsafe { GetCursorPos(&mut point) }
+            .ok()
+            .map(|_| ClickPoint { x: point.x, y: point.y })
+    }
+
     pub fn is_running(&self) -> bool {
-        self.is_running.load(Ordering::Relaxed)
+        self.run.is_running.load(Ordering::Relaxed)
+    }
+
+    /// 是否因连续注入失败而自动停止
+    pub fn injection_failed(&self) -> bool {
+        self.run.injection_failed.load(Ordering::Relaxed)
+    }
+
+    /// 本次运行中系统是否丢弃过点击事件并因此自动降速，此时 CPS 为实际达到的频率
+    pub fn throttled(&self) -> bool {
+        self.run.throttled.load(Ordering::Relaxed)
+    }
+
+    /// 是否因达到点击次数上限而停止
+    pub fn limit_reached(&self) -> bool {
+        self.run.limit_reached.load(Ordering::Relaxed)
     }
 
     pub fn get_click_count(&self) -> u64 {
-        self.click_count.load(Ordering::Relaxed)
+        self.run.click_count.load(Ordering::Relaxed)
     }
 
+    /// 本次运行的时长，暂停冻结统计时不包含暂停的时间
     pub fn get_running_time(&self) -> Option<Duration> {
-        self.start_time.map(|start| start.elapsed())
+        let elapsed = self.start_time?.elapsed();
+        if !self.pause_freezes_stats {
+            return Some(elapsed);
+        }
+        let paused = self.paused_total + self.paused_since.map_or(Duration::ZERO, |since| since.elapsed());
+        Some(elapsed.saturating_sub(paused))
     }
 
     pub fn get_cps(&self) -> f64 {
@@ -136,20 +969,11 @@ impl MouseController {
         }
         0.0
     }
+}
 
-    fn simulate_click(button: MouseButton) {
-        unsafe {
-            match button {
-                MouseButton::Left => {
-                    mouse_event(MOUSEEVENTF_LEFTDOWN, 0, 0, 0, 0);
-                    mouse_event(MOUSEEVENTF_LEFTUP, 0, 0, 0, 0);
-                }
-                MouseButton::Right => {
-                    mouse_event(MOUSEEVENTF_RIGHTDOWN, 0, 0, 0, 0);
-                    mouse_event(MOUSEEVENTF_RIGHTUP, 0, 0, 0, 0);
-                }
-            }
-        }
+impl Default for MouseController {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -158,3 +982,555 @@ impl Drop for MouseController {
         self.stop_clicking();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ScrollDirection;
+    use crate::input_backend::{MockBackend, MockEvent};
+
+    /// 等待条件成立，最多等待 5 秒，超时返回 false
+    fn wait_for(mut condition: impl FnMut() -> bool) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !condition() {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        true
+    }
+
+    #[test]
+    fn stops_after_repeated_injection_failures() {
+        let backend = Arc::new(MockBackend::failing());
+        let mut controller = MouseController::with_backend(backend);
+
+        controller.start_clicking(ClickSettings::new(MouseButton::Left, 1));
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while controller.is_running() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(!controller.is_running());
+        assert!(controller.injection_failed());
+        assert_eq!(controller.get_click_count(), 0);
+        controller.stop_clicking();
+    }
+
+    #[test]
+    fn counts_only_injected_clicks() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        controller.start_clicking(ClickSettings::new(MouseButton::Left, 5));
+        thread::sleep(Duration::from_millis(100));
+        controller.stop_clicking();
+
+        assert!(!controller.injection_failed());
+        assert!(controller.get_click_count() > 0);
+        assert_eq!(controller.get_click_count(), backend.clicks.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn macro_runs_steps_in_a_loop() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        controller.start_macro(vec![
+            ActionStep::KeyPress { key: "R".to_string() },
+            ActionStep::MouseClick { button: MouseButton::Left },
+            ActionStep::Wait { ms: 10 },
+        ]);
+        thread::sleep(Duration::from_millis(100));
+        controller.stop_clicking();
+
+        assert!(controller.get_click_count() >= 2);
+        assert_eq!(controller.get_click_count(), backend.clicks.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn macro_counts_each_scroll_notch() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        controller.start_macro(vec![
+            ActionStep::Scroll { direction: ScrollDirection::Horizontal, amount: -3 },
+            ActionStep::Wait { ms: 1000 },
+        ]);
+        thread::sleep(Duration::from_millis(100));
+        controller.stop_clicking();
+
+        assert_eq!(controller.get_click_count(), 3);
+        assert_eq!(backend.scrolls.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn refuses_to_start_in_quiet_hours() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        let now = TimeOfDay::now();
+        controller.set_quiet_hours(Some(QuietHours {
+            start: now,
+            end: TimeOfDay::new((now.hour + 2) % 24, now.minute),
+        }));
+        controller.start_clicking(ClickSettings::new(MouseButton::Left, 10));
+        thread::sleep(Duration::from_millis(50));
+
+        assert!(!controller.is_running());
+        assert!(controller.stopped_by_quiet_hours());
+        assert_eq!(backend.clicks.load(Ordering::Relaxed), 0);
+
+        controller.set_quiet_hours(None);
+        controller.start_clicking(ClickSettings::new(MouseButton::Left, 10));
+        assert!(controller.is_running());
+        assert!(!controller.stopped_by_quiet_hours());
+        controller.stop_clicking();
+    }
+
+    #[test]
+    fn stopping_during_start_jitter_skips_the_first_click() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        let mut settings = ClickSettings::new(MouseButton::Left, 10);
+        settings.start_jitter_ms = (300, 400);
+        controller.start_clicking(settings);
+        thread::sleep(Duration::from_millis(100));
+        controller.stop_clicking();
+
+        assert_eq!(backend.clicks.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn random_between_handles_full_u64_range() {
+        for _ in 0..5 {
+            assert!((300..=400).contains(&random_between(300, 400)));
+        }
+        assert_eq!(random_between(7, 7), 7);
+        assert!(random_between(u64::MAX - 1, u64::MAX) >= u64::MAX - 1);
+        // 整个 u64 区间不溢出，结果也不会被截断到低位
+        assert!((0..8).any(|_| random_between(0, u64::MAX) > u64::from(u32::MAX)));
+    }
+
+    #[test]
+    fn pattern_skips_slots() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        // 点一格跳三格
+        let mut settings = ClickSettings::new(MouseButton::Left, 20);
+        settings.pattern = vec![true, false, false, false];
+        controller.start_clicking(settings);
+        assert!(wait_for(|| backend.clicks.load(Ordering::Relaxed) >= 4));
+        controller.stop_clicking();
+
+        // 每次点击之后的三格都被跳过，相邻两次点击至少相隔四格（允许半格的计时误差）
+        let times: Vec<Instant> = backend.events.lock().unwrap().iter().map(|(time, _)| *time).collect();
+        for pair in times.windows(2) {
+            let gap = pair[1] - pair[0];
+            assert!(gap >= Duration::from_millis(70), "相邻点击只相隔 {:?}", gap);
+        }
+        assert_eq!(controller.get_click_count(), times.len() as u64);
+    }
+
+    #[test]
+    fn pause_stops_clicks_and_optionally_freezes_time() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend);
+
+        controller.start_clicking(ClickSettings::new(MouseButton::Left, 5));
+        thread::sleep(Duration::from_millis(100));
+        controller.pause();
+        thread::sleep(Duration::from_millis(20));
+        let clicks = controller.get_click_count();
+        thread::sleep(Duration::from_millis(200));
+        assert!(controller.is_paused());
+        assert_eq!(controller.get_click_count(), clicks);
+
+        // 默认冻结统计：运行时间不包含暂停的 200ms
+        let frozen = controller.get_running_time().unwrap();
+        assert!(frozen < Duration::from_millis(250), "运行时间 {:?}", frozen);
+        controller.set_pause_freezes_stats(false);
+        assert!(controller.get_running_time().unwrap() >= Duration::from_millis(300));
+        controller.set_pause_freezes_stats(true);
+
+        controller.resume();
+        thread::sleep(Duration::from_millis(100));
+        controller.stop_clicking();
+        assert!(controller.get_click_count() > clicks);
+        assert!(!controller.is_paused());
+    }
+
+    #[test]
+    fn humanized_clicks_hold_and_release() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        let mut settings = ClickSettings::new(MouseButton::Left, 40);
+        settings.points = vec![ClickPoint { x: 100, y: 100 }];
+        settings.position_jitter = 3;
+        settings.hold_ms = (5, 10);
+        settings.interval_jitter_pct = 20;
+        controller.start_clicking(settings);
+        assert!(wait_for(|| controller.get_click_count() >= 5));
+        controller.stop_clicking();
+
+        // 每次点击都是移到偏移后的坐标、按下、按住一段时间后松开
+        let events = backend.events.lock().unwrap().clone();
+        assert_eq!(events.len() as u64, controller.get_click_count() * 3, "{:?}", events);
+        let last = events.len() / 3 - 1;
+        for (i, click) in events.chunks(3).enumerate() {
+            let [
+                (_, MockEvent::Move(point)),
+                (down_at, MockEvent::Button(MouseButton::Left, true)),
+                (up_at, MockEvent::Button(MouseButton::Left, false)),
+            ] = click
+            else {
+                panic!("点击序列不完整: {:?}", click);
+            };
+            assert!((97..=103).contains(&point.x) && (97..=103).contains(&point.y), "{:?}", point);
+            // 按住不短于最小时长，也不会拖到下一次点击；最后一次可能被停止提前松开
+            let hold = *up_at - *down_at;
+            assert!(hold < Duration::from_millis(40), "按住了 {:?}", hold);
+            assert!(i == last || hold >= Duration::from_millis(5), "按住了 {:?}", hold);
+        }
+        assert_eq!(backend.held.load(Ordering::Relaxed), 0);
+
+        // 停止时不等完按住时长，按键仍会松开
+        let backend = MockBackend::new();
+        let stopped = AtomicBool::new(false);
+        let start = Instant::now();
+        assert!(MouseController::held_click(&backend, &stopped, MouseButton::Left, None, (1000, 1000), 10_000));
+        assert!(start.elapsed() < STOP_TIMEOUT);
+        assert_eq!(
+            backend.events(),
+            [MockEvent::Button(MouseButton::Left, true), MockEvent::Button(MouseButton::Left, false)]
+        );
+
+        for _ in 0..20 {
+            let point = jittered_point(ClickPoint { x: 100, y: 100 }, 3);
+            assert!((97..=103).contains(&point.x) && (97..=103).contains(&point.y));
+            let interval = jittered_interval(Duration::from_millis(100), 20);
+            assert!((80..=120).contains(&interval.as_millis()));
+        }
+    }
+
+    #[test]
+    fn sleep_returns_when_stopped() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        // 第一次点击后线程进入 10 秒的间隔睡眠
+        controller.start_clicking(ClickSettings::new(MouseButton::Left, 10_000));
+        assert!(wait_for(|| controller.get_click_count() == 1));
+        thread::sleep(Duration::from_millis(50));
+
+        // 分段睡眠在一个分段内看到停止，线程被等到结束而不是超时放弃
+        let stop_start = Instant::now();
+        controller.stop_clicking();
+        let elapsed = stop_start.elapsed();
+        assert!(elapsed < STOP_TIMEOUT, "停止耗时 {:?}", elapsed);
+        assert_eq!(backend.clicks.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn injection_cost_moves_the_schedule_earlier() {
+        let interval = Duration::from_millis(20);
+        let next_click = Instant::now() + Duration::from_secs(1);
+        let mut cost = InjectionCost::default();
+        assert_eq!(cost.fire_at(next_click), next_click);
+
+        // 每次注入耗时 8ms，平均值收敛后提前约 8ms 开始注入
+        for _ in 0..64 {
+            cost.record(Duration::from_millis(8), interval);
+        }
+        let early = next_click - cost.fire_at(next_click);
+        assert!((Duration::from_micros(7900)..=Duration::from_millis(8)).contains(&early), "提前了 {:?}", early);
+
+        // 耗时超过间隔时最多提前一个间隔
+        for _ in 0..64 {
+            cost.record(Duration::from_millis(500), interval);
+        }
+        assert_eq!(next_click - cost.fire_at(next_click), interval);
+    }
+
+    #[test]
+    fn stops_exactly_at_click_limit_across_threads() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        // 1ms 间隔会启用多个线程
+        let mut settings = ClickSettings::new(MouseButton::Left, 1);
+        settings.click_limit = 25;
+        controller.start_clicking(settings);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while controller.is_running() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(controller.limit_reached());
+        assert!(!controller.is_running());
+        assert_eq!(controller.get_click_count(), 25);
+        assert_eq!(backend.clicks.load(Ordering::Relaxed), 25);
+        controller.stop_clicking();
+    }
+
+    #[test]
+    fn restart_on_limit_keeps_clicking() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        let mut settings = ClickSettings::new(MouseButton::Left, 5);
+        settings.click_limit = 5;
+        settings.on_limit = LimitAction::Restart;
+        controller.start_clicking(settings);
+        thread::sleep(Duration::from_millis(200));
+
+        assert!(controller.is_running());
+        assert!(!controller.limit_reached());
+        assert!(controller.get_click_count() > 5);
+        controller.stop_clicking();
+    }
+
+    #[test]
+    fn drag_releases_button_when_stopped_midway() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        // 100 像素/秒走 1000 像素需要 10 秒，移动几步后中途停止
+        controller.start_drag(DragSettings {
+            button: MouseButton::Left,
+            path: vec![ClickPoint { x: 0, y: 0 }, ClickPoint { x: 1000, y: 0 }],
+            speed: 100,
+            pause_ms: 0,
+        });
+        assert!(wait_for(|| backend.moves.load(Ordering::Relaxed) > 3));
+        assert_eq!(backend.held.load(Ordering::Relaxed), 1);
+        controller.stop_clicking();
+
+        // 移到起点后按下，沿路径向右移动，停止时松开
+        let events = backend.events();
+        assert_eq!(events[0], MockEvent::Move(ClickPoint { x: 0, y: 0 }));
+        assert_eq!(events[1], MockEvent::Button(MouseButton::Left, true));
+        assert_eq!(events.last(), Some(&MockEvent::Button(MouseButton::Left, false)));
+        let mut last_x = 0;
+        for event in &events[2..events.len() - 1] {
+            let MockEvent::Move(point) = *event else {
+                panic!("拖拽中出现了 {:?}", event);
+            };
+            assert!(point.y == 0 && point.x >= last_x, "{:?}", point);
+            last_x = point.x;
+        }
+        assert_eq!(backend.held.load(Ordering::Relaxed), 0);
+        assert_eq!(controller.get_click_count(), 0);
+    }
+
+    #[test]
+    fn drag_repeats_complete_gestures() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        controller.start_drag(DragSettings {
+            button: MouseButton::Left,
+            path: vec![ClickPoint { x: 0, y: 0 }, ClickPoint { x: 10, y: 10 }],
+            speed: 10_000,
+            pause_ms: 10,
+        });
+        assert!(wait_for(|| controller.get_click_count() >= 3));
+        controller.stop_clicking();
+
+        // 每次拖拽都是移到起点、按下、移到终点、松开，最后一次可能在计数前被停止
+        let events = backend.events();
+        let gesture = [
+            MockEvent::Move(ClickPoint { x: 0, y: 0 }),
+            MockEvent::Button(MouseButton::Left, true),
+            MockEvent::Move(ClickPoint { x: 10, y: 10 }),
+            MockEvent::Button(MouseButton::Left, false),
+        ];
+        assert_eq!(events.len() % gesture.len(), 0, "{:?}", events);
+        assert!(events.chunks(gesture.len()).all(|chunk| chunk == gesture), "{:?}", events);
+        let gestures = (events.len() / gesture.len()) as u64;
+        let clicks = controller.get_click_count();
+        assert!(gestures == clicks || gestures == clicks + 1, "{} 次拖拽，计数 {}", gestures, clicks);
+        assert_eq!(backend.held.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn dry_run_mode_does_not_touch_the_real_backend() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        let mut settings = ClickSettings::new(MouseButton::Left, 10);
+        settings.mode = RunMode::DryRun;
+        controller.start_clicking(settings);
+        thread::sleep(Duration::from_millis(100));
+        controller.stop_clicking();
+
+        assert!(controller.get_click_count() > 0);
+        assert_eq!(backend.clicks.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn single_shot_mode_clicks_once() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        let mut settings = ClickSettings::new(MouseButton::Left, 1);
+        settings.mode = RunMode::SingleShot;
+        controller.start_clicking(settings);
+        thread::sleep(Duration::from_millis(100));
+
+        assert!(!controller.is_running());
+        assert_eq!(backend.clicks.load(Ordering::Relaxed), 1);
+        controller.stop_clicking();
+    }
+
+    #[test]
+    fn rhythm_test_clicks_in_groups_then_stops() {
+        let backend = Arc::new(MockBackend::new());
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        let mut settings = ClickSettings::new(MouseButton::Left, 20);
+        settings.mode = RunMode::RhythmTest;
+        controller.start_clicking(settings);
+        assert!(wait_for(|| !controller.is_running()));
+        controller.stop_clicking();
+
+        assert!(controller.limit_reached());
+        assert_eq!(backend.clicks.load(Ordering::Relaxed), RHYTHM_TEST_CLICKS);
+        // 每组三下之后停顿两格，下一组的第一下与上一组最后一下至少相隔三格
+        let times: Vec<Instant> = backend.events.lock().unwrap().iter().map(|(time, _)| *time).collect();
+        for group in times.chunks(3).collect::<Vec<_>>().windows(2) {
+            let pause = group[1][0] - group[0][2];
+            assert!(pause >= Duration::from_millis(50), "组间只停顿了 {:?}", pause);
+        }
+    }
+
+    #[test]
+    fn dropped_events_slow_down_instead_of_counting() {
+        let backend = Arc::new(MockBackend::new());
+        backend.drop_events.store(true, Ordering::Relaxed);
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        controller.start_clicking(ClickSettings::new(MouseButton::Left, 1));
+        thread::sleep(Duration::from_millis(200));
+
+        // 部分丢弃不算点击，也不会像完全注入失败那样停止
+        assert!(controller.is_running());
+        assert!(controller.throttled());
+        assert_eq!(controller.get_click_count(), 0);
+        assert!(!controller.injection_failed());
+
+        backend.drop_events.store(false, Ordering::Relaxed);
+        thread::sleep(Duration::from_millis(300));
+        controller.stop_clicking();
+
+        let clicks = controller.get_click_count();
+        assert!(clicks > 0);
+        assert_eq!(clicks, backend.clicks.load(Ordering::Relaxed));
+        // 丢弃了松开事件的点击都补发了松开，按键最终处于松开状态
+        let events = backend.events();
+        assert!(events.contains(&MockEvent::Button(MouseButton::Left, false)));
+        assert_eq!(backend.held.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn point_orders_visit_every_point_each_cycle() {
+        let reverse = PointCursor::new(PointOrder::Reverse, 3);
+        let visited: Vec<usize> = (0..6).map(|_| reverse.next()).collect();
+        assert_eq!(visited, [2, 1, 0, 2, 1, 0]);
+
+        let random = PointCursor::new(PointOrder::Random, 5);
+        for _ in 0..4 {
+            let mut cycle: Vec<usize> = (0..5).map(|_| random.next()).collect();
+            cycle.sort_unstable();
+            assert_eq!(cycle, [0, 1, 2, 3, 4]);
+        }
+
+        // 其他线程已进入下一轮时，上一轮最后取号的线程仍按上一轮的排列取坐标
+        let random = PointCursor::new(PointOrder::Random, 5);
+        let mut first: Vec<usize> = (0..4).map(|n| random.index_for(n)).collect();
+        let second_start = random.index_for(5);
+        first.push(random.index_for(4));
+        first.sort_unstable();
+        assert_eq!(first, [0, 1, 2, 3, 4]);
+        let mut second: Vec<usize> = (6..10).map(|n| random.index_for(n)).collect();
+        second.push(second_start);
+        second.sort_unstable();
+        assert_eq!(second, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn bezier_path_bends_and_ends_on_target() {
+        let from = ClickPoint { x: 0, y: 0 };
+        let to = ClickPoint { x: 100, y: 0 };
+        let path = bezier_path(from, (50.0, 40.0), to, 10);
+
+        assert_eq!(path.len(), 10);
+        assert_eq!(*path.last().unwrap(), to);
+        // 曲线向控制点一侧弯曲，中点偏离直线
+        assert_eq!(path[4], ClickPoint { x: 50, y: 20 });
+        assert!(path.windows(2).all(|pair| pair[0].x < pair[1].x));
+    }
+
+    #[test]
+    fn glide_moves_through_backend_and_stops() {
+        let from = ClickPoint { x: 0, y: 0 };
+        let to = ClickPoint { x: 100, y: 0 };
+        let backend = MockBackend::new();
+        let is_running = AtomicBool::new(true);
+
+        assert!(MouseController::glide(&backend, &is_running, from, to, 10_000, Duration::from_millis(50)));
+        let events = backend.events();
+        assert!(events.len() > 1);
+        assert!(events.iter().all(|event| matches!(event, MockEvent::Move(_))));
+        assert_eq!(events.last(), Some(&MockEvent::Move(to)));
+
+        // 停止后移动一步就返回，不再继续移动
+        let backend = MockBackend::new();
+        is_running.store(false, Ordering::Relaxed);
+        assert!(!MouseController::glide(&backend, &is_running, from, to, 10_000, Duration::from_millis(50)));
+        assert_eq!(backend.events().len(), 1);
+
+        // 移动失败时返回 false
+        let backend = MockBackend::failing();
+        is_running.store(true, Ordering::Relaxed);
+        assert!(!MouseController::glide(&backend, &is_running, from, to, 10_000, Duration::from_millis(50)));
+    }
+
+    #[test]
+    fn stop_does_not_wait_for_a_stuck_worker() {
+        let backend = Arc::new(MockBackend::new());
+        backend.blocking.store(true, Ordering::Relaxed);
+        let mut controller = MouseController::with_backend(backend.clone());
+
+        controller.start_clicking(ClickSettings::new(MouseButton::Left, 50));
+        assert!(wait_for(|| backend.blocked.load(Ordering::Relaxed) == 1));
+
+        // 工作线程仍卡在注入中，停止不等待它结束
+        controller.stop_clicking();
+        assert!(!controller.is_running());
+        assert_eq!(controller.thread_count(), 0);
+        assert_eq!(backend.blocked.load(Ordering::Relaxed), 1);
+        assert!(backend.events().is_empty());
+
+        // 卡住的注入返回时松开被丢弃，由旧线程自己补发松开，且只松开设置的按键
+        let clicks = controller.get_click_count();
+        backend.drop_events.store(true, Ordering::Relaxed);
+        backend.blocking.store(false, Ordering::Relaxed);
+        assert!(wait_for(|| backend.events().len() == 2));
+        assert_eq!(
+            backend.events(),
+            [MockEvent::Button(MouseButton::Left, true), MockEvent::Button(MouseButton::Left, false)]
+        );
+        assert_eq!(backend.held.load(Ordering::Relaxed), 0);
+
+        // 旧线程的降速和计数不会出现在已停止的运行结果中
+        assert!(!controller.throttled());
+        assert_eq!(controller.get_click_count(), clicks);
+    }
+}
@@ -0,0 +1,187 @@
+use std::sync::Once;
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM};
+use windows::Win32::Graphics::Gdi::{
+    BeginPaint, CreateFontW, CreateSolidBrush, DeleteObject, DrawTextW, EndPaint, FillRect,
+    InvalidateRect, SelectObject, SetBkMode, SetTextColor, DEFAULT_CHARSET, DT_CENTER,
+    DT_SINGLELINE, DT_VCENTER, FW_BOLD, PAINTSTRUCT, TRANSPARENT,
+};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DestroyWindow, GetClientRect, GetSystemMetrics,
+    GetWindowLongPtrW, RegisterClassW, SetLayeredWindowAttributes, SetWindowLongPtrW, ShowWindow,
+    GWLP_USERDATA, HTTRANSPARENT, LWA_ALPHA, LWA_COLORKEY, SM_CXSCREEN, SM_CYSCREEN,
+    SW_SHOWNOACTIVATE, WM_NCHITTEST, WM_PAINT, WNDCLASSW, WS_EX_LAYERED, WS_EX_NOACTIVATE,
+    WS_EX_TOOLWINDOW, WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_POPUP,
+};
+
+const CLASS_NAME: PCWSTR = w!("RustAcOverlay");
+
+/// 作为透明色的背景色，绘制时不会显示
+const COLOR_KEY: COLORREF = COLORREF(0x000000);
+
+static REGISTER_CLASS: Once = Once::new();
+
+/// 覆盖层绘制内容
+struct OverlayState {
+    text: Vec<u16>,
+    text_color: COLORREF,
+    font_height: i32,
+}
+
+/// 置顶、不抢焦点、鼠标穿透的覆盖层窗口
+///
+/// 窗口设置了 `WS_EX_LAYERED | WS_EX_TRANSPARENT`，鼠标输入会直接落到下方程序。
+/// 必须在创建它的线程（即界面线程）上使用和销毁，析构时自动关闭窗口。
+pub struct OverlayWindow {
+    hwnd: HWND,
+    state: *mut OverlayState,
+}
+
+impl OverlayWindow {
+    /// 在屏幕区域上创建覆盖层，alpha 为整体不透明度
+    pub fn create(x: i32, y: i32, width: i32, height: i32, alpha: u8) -> Result<Self, String> {
+        let instance = unsafe { GetModuleHandleW(None) }
+            .map_err(|e| format!("获取模块句柄失败: {}", e))?;
+
+        REGISTER_CLASS.call_once(|| {
+            let class = WNDCLASSW {
+                lpfnWndProc: Some(overlay_wnd_proc),
+                hInstance: instance.into(),
+                lpszClassName: CLASS_NAME,
+                ..Default::default()
+            };
+            unsafe { RegisterClassW(&class) };
+        });
+
+        let hwnd = unsafe {
+            CreateWindowExW(
+                WS_EX_LAYERED | WS_EX_TRANSPARENT | WS_EX_TOPMOST | WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE,
+                CLASS_NAME,
+                w!("Rust-AC Overlay"),
+                WS_POPUP,
+                x,
+                y,
+                width,
+                height,
+                None,
+                None,
+                instance,
+                None,
+            )
+        };
+        if hwnd.0 == 0 {
+            return Err("创建覆盖层窗口失败".to_string());
+        }
+
+        let state = Box::into_raw(Box::new(OverlayState {
+            text: Vec::new(),
+            text_color: COLORREF(0x00FFFFFF),
+            font_height: height / 2,
+        }));
+
+        unsafe {
+            SetWindowLongPtrW(hwnd, GWLP_USERDATA, state as isize);
+            // 背景色抠为透明，其余部分按 alpha 半透明显示
+            if let Err(e) = SetLayeredWindowAttributes(hwnd, COLOR_KEY, alpha, LWA_COLORKEY | LWA_ALPHA) {
+                crate::log_warn!("设置覆盖层透明度失败: {}", e);
+            }
+            ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+        }
+
+        Ok(Self { hwnd, state })
+    }
+
+    /// 在主显示器中央创建指定大小的覆盖层
+    pub fn centered(width: i32, height: i32, alpha: u8) -> Result<Self, String> {
+        let (screen_width, screen_height) =
+            unsafe { (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)) };
+        Self::create(
+            (screen_width - width) / 2,
+            (screen_height - height) / 2,
+            width,
+            height,
+            alpha,
+        )
+    }
+
+    /// 更新居中显示的文字，color 为 0x00BBGGRR 格式
+    pub fn set_text(&mut self, text: &str, color: u32) {
+        unsafe {
+            let state = &mut *self.state;
+            state.text = text.encode_utf16().collect();
+            state.text_color = COLORREF(color);
+            InvalidateRect(self.hwnd, None, true);
+        }
+    }
+}
+
+impl Drop for OverlayWindow {
+    fn drop(&mut self) {
+        unsafe {
+            SetWindowLongPtrW(self.hwnd, GWLP_USERDATA, 0);
+            let _ = DestroyWindow(self.hwnd);
+            drop(Box::from_raw(self.state));
+        }
+    }
+}
+
+unsafe extern "system" fn overlay_wnd_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        // 双重保险：命中测试直接穿透
+        WM_NCHITTEST => LRESULT(HTTRANSPARENT as isize),
+        WM_PAINT => {
+            unsafe { paint(hwnd) };
+            LRESULT(0)
+        }
+        _ => unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) },
+    }
+}
+
+unsafe fn paint(hwnd: HWND) {
+    unsafe {
+        let mut ps = PAINTSTRUCT::default();
+        let hdc = BeginPaint(hwnd, &mut ps);
+
+        let mut rect = RECT::default();
+        let _ = GetClientRect(hwnd, &mut rect);
+        let background = CreateSolidBrush(COLOR_KEY);
+        FillRect(hdc, &rect, background);
+        DeleteObject(background);
+
+        let state = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *mut OverlayState;
+        if let Some(state) = state.as_mut()
+            && !state.text.is_empty()
+        {
+            let font = CreateFontW(
+                state.font_height,
+                0,
+                0,
+                0,
+                FW_BOLD.0 as i32,
+                0,
+                0,
+                0,
+                DEFAULT_CHARSET.0 as u32,
+                0,
+                0,
+                0,
+                0,
+                w!("Microsoft YaHei"),
+            );
+            let old_font = SelectObject(hdc, font);
+            SetBkMode(hdc, TRANSPARENT);
+            SetTextColor(hdc, state.text_color);
+            DrawTextW(hdc, &mut state.text, &mut rect, DT_CENTER | DT_VCENTER | DT_SINGLELINE);
+            SelectObject(hdc, old_font);
+            DeleteObject(font);
+        }
+
+        EndPaint(hwnd, &ps);
+    }
+}
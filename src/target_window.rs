@@ -0,0 +1,106 @@
+use crate::config::ClickPoint;
+use std::sync::atomic::{AtomicIsize, Ordering};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{HWND, POINT, RECT};
+use windows::Win32::Graphics::Gdi::{ClientToScreen, ScreenToClient};
+use windows::Win32::UI::WindowsAndMessaging::{
+    FindWindowW, GetAncestor, GetClientRect, GetForegroundWindow, GetWindowTextW, IsIconic,
+    IsWindow, WindowFromPoint, GA_ROOT,
+};
+
+/// 按标题查找顶层窗口
+pub fn find_window(title: &str) -> Option<HWND> {
+    let wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+    let hwnd = unsafe { FindWindowW(PCWSTR::null(), PCWSTR(wide.as_ptr())) };
+    if hwnd.0 == 0 { None } else { Some(hwnd) }
+}
+
+/// 当前的前台窗口
+///
+/// 切换焦点的瞬间系统可能没有前台窗口，此时返回 None
+pub fn foreground_window() -> Option<HWND> {
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.0 == 0 { None } else { Some(hwnd) }
+}
+
+/// 读取窗口标题
+pub fn window_title(hwnd: HWND) -> String {
+    let mut buf = [0u16; 256];
+    let len = unsafe { GetWindowTextW(hwnd, &mut buf) };
+    String::from_utf16_lossy(&buf[..len.max(0) as usize])
+}
+
+/// 按标题绑定的目标窗口
+///
+/// 开始时查找一次窗口句柄，之后每次只检查句柄是否仍然有效，窗口关闭后再按标题重新查找
+pub struct TargetWindow {
+    title: String,
+    hwnd: AtomicIsize,
+}
+
+impl TargetWindow {
+    pub fn new(title: String) -> Self {
+        let hwnd = find_window(&title).map_or(0, |hwnd| hwnd.0);
+        Self { title, hwnd: AtomicIsize::new(hwnd) }
+    }
+
+    /// 当前有效的窗口句柄，找不到窗口时返回 None
+    pub fn hwnd(&self) -> Option<HWND> {
+        let cached = HWND(self.hwnd.load(Ordering::Relaxed));
+        if cached.0 != 0 && unsafe { IsWindow(cached) }.as_bool() {
+            return Some(cached);
+        }
+        let hwnd = find_window(&self.title)?;
+        self.hwnd.store(hwnd.0, Ordering::Relaxed);
+        Some(hwnd)
+    }
+
+    /// 将相对窗口客户区的坐标换算为屏幕坐标
+    pub fn client_to_screen(&self, point: ClickPoint) -> Option<ClickPoint> {
+        client_to_screen(self.hwnd()?, point)
+    }
+}
+
+/// 将相对窗口客户区的坐标换算为屏幕坐标
+///
+/// 窗口已最小化或坐标超出客户区时返回 None
+pub fn client_to_screen(hwnd: HWND, point: ClickPoint) -> Option<ClickPoint> {
+    unsafe {
+        if IsIconic(hwnd).as_bool() {
+            return None;
+        }
+        let mut rect = RECT::default();
+        GetClientRect(hwnd, &mut rect).ok()?;
+        if point.x < 0 || point.y < 0 || point.x >= rect.right || point.y >= rect.bottom {
+            return None;
+        }
+        let mut pt = POINT { x: point.x, y: point.y };
+        if !ClientToScreen(hwnd, &mut pt).as_bool() {
+            return None;
+        }
+        Some(ClickPoint { x: pt.x, y: pt.y })
+    }
+}
+
+/// 获取屏幕坐标下方的顶层窗口，返回窗口标题及该点相对其客户区的坐标
+pub fn window_at(point: ClickPoint) -> Option<(String, ClickPoint)> {
+    unsafe {
+        let child = WindowFromPoint(POINT { x: point.x, y: point.y });
+        if child.0 == 0 {
+            return None;
+        }
+        let hwnd = GetAncestor(child, GA_ROOT);
+        if hwnd.0 == 0 {
+            return None;
+        }
+        let title = window_title(hwnd);
+        if title.is_empty() {
+            return None;
+        }
+        let mut pt = POINT { x: point.x, y: point.y };
+        if !ScreenToClient(hwnd, &mut pt).as_bool() {
+            return None;
+        }
+        Some((title, ClickPoint { x: pt.x, y: pt.y }))
+    }
+}
@@ -1,5 +1,5 @@
 use tray_icon::{
-    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu},
     TrayIcon, TrayIconBuilder, Icon,
 };
 use std::sync::mpsc::{self, Receiver};
@@ -9,6 +9,7 @@ pub enum TrayEvent {
     HideWindow,
     StartClicking,
     StopClicking,
+    SwitchProfile(String),
     Quit,
 }
 
@@ -20,6 +21,9 @@ pub struct TrayManager {
     start_item_id: tray_icon::menu::MenuId,
     stop_item_id: tray_icon::menu::MenuId,
     quit_item_id: tray_icon::menu::MenuId,
+    quit_item: MenuItem,
+    profile_menu: Submenu,
+    profile_items: Vec<(CheckMenuItem, String)>,
 }
 
 impl TrayManager {
@@ -34,6 +38,7 @@ impl TrayManager {
         let start_item = MenuItem::new("切换连点", true, None);
         let stop_item = MenuItem::new("停止连点", true, None);
         let quit_item = MenuItem::new("退出程序", true, None);
+        let profile_menu = Submenu::new("配置方案", true);
 
         let show_item_id = show_item.id().clone();
         let hide_item_id = hide_item.id().clone();
@@ -48,6 +53,8 @@ impl TrayManager {
         menu.append(&start_item).map_err(|e| format!("添加开始菜单项失败: {}", e))?;
         menu.append(&stop_item).map_err(|e| format!("添加停止菜单项失败: {}", e))?;
         menu.append(&PredefinedMenuItem::separator()).map_err(|e| format!("添加分隔符失败: {}", e))?;
+        menu.append(&profile_menu).map_err(|e| format!("添加配置方案菜单失败: {}", e))?;
+        menu.append(&PredefinedMenuItem::separator()).map_err(|e| format!("添加分隔符失败: {}", e))?;
         menu.append(&quit_item).map_err(|e| format!("添加退出菜单项失败: {}", e))?;
 
         // 设置菜单事件处理器
@@ -78,6 +85,9 @@ impl TrayManager {
             start_item_id,
             stop_item_id,
             quit_item_id,
+            quit_item,
+            profile_menu,
+            profile_items: Vec::new(),
         })
     }
 
@@ -139,6 +149,31 @@ impl TrayManager {
         let _ = self.tray_icon.set_tooltip(Some(&tooltip));
     }
 
+    /// 等待二次确认时把退出菜单项改为提示文字，取消后恢复
+    pub fn set_quit_armed(&self, armed: bool) {
+        self.quit_item.set_text(if armed { "再次点击确认退出（正在连点）" } else { "退出程序" });
+    }
+
+    /// 重建配置方案子菜单，勾选当前方案
+    pub fn set_profiles(&mut self, profiles: &[String], active: Option<&str>) {
+        while self.profile_menu.remove_at(0).is_some() {}
+        self.profile_items.clear();
+
+        if profiles.is_empty() {
+            let _ = self.profile_menu.append(&MenuItem::new("(无配置方案)", false, None));
+            return;
+        }
+
+        for name in profiles {
+            let item = CheckMenuItem::new(name, true, Some(name.as_str()) == active, None);
+            if let Err(e) = self.profile_menu.append(&item) {
+                rust_ac::log_warn!("添加配置方案菜单项失败: {}", e);
+                continue;
+            }
+            self.profile_items.push((item, name.clone()));
+        }
+    }
+
     pub fn check_events(&self) -> Option<TrayEvent> {
         if let Ok(event) = self.receiver.try_recv() {
             if event.id == self.show_item_id {
@@ -151,6 +186,12 @@ impl TrayManager {
                 return Some(TrayEvent::StopClicking);
             } else if event.id == self.quit_item_id {
                 return Some(TrayEvent::Quit);
+            } else if let Some((_, name)) = self
+                .profile_items
+                .iter()
+                .find(|(item, _)| item.id() == &event.id)
+            {
+                return Some(TrayEvent::SwitchProfile(name.clone()));
             }
         }
         None
@@ -0,0 +1,45 @@
+//! 界面线程繁忙时点击节奏不受影响
+
+use rust_ac::{ClickSettings, DryRunBackend, MouseButton, MouseController};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 在 duration 内反复执行 work，返回期间新增的点击次数
+fn clicks_during(controller: &MouseController, duration: Duration, mut work: impl FnMut()) -> u64 {
+    let before = controller.get_click_count();
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        work();
+    }
+    controller.get_click_count() - before
+}
+
+#[test]
+fn cadence_is_stable_while_ui_repaints_continuously() {
+    let backend = Arc::new(DryRunBackend::new());
+    let mut controller = MouseController::with_backend(backend);
+    controller.start_clicking(ClickSettings::new(MouseButton::Left, 10));
+
+    let idle = clicks_during(&controller, Duration::from_millis(500), || {
+        thread::sleep(Duration::from_millis(1));
+    });
+
+    // 模拟界面线程不停地请求重绘并运行帧
+    let ctx = egui::Context::default();
+    let mut frames = 0u64;
+    let busy = clicks_during(&controller, Duration::from_millis(500), || {
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.label(format!("第 {} 帧", frames));
+            });
+            ctx.request_repaint();
+        });
+        frames += 1;
+    });
+    controller.stop_clicking();
+
+    // 与界面空闲时相比，繁忙时的点击次数基本不变
+    assert!(frames > 0);
+    assert!(busy * 10 >= idle * 9, "空闲时 {} 次，繁忙时 {} 次", idle, busy);
+}
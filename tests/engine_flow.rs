@@ -0,0 +1,138 @@
+//! `MouseController` 与 `HotkeyManager` 的完整流程：开始、停止、长按热键、点击上限及配置读写
+//!
+//! 使用 test-util 特性提供的模拟后端，不产生真实输入，也不依赖界面
+
+use rust_ac::input_backend::MockBackend;
+use rust_ac::{
+    AppConfig, ClickPoint, ClickSettings, HotkeyAction, HotkeyConfig, HotkeyManager, LimitAction, MouseButton,
+    MouseController,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn controller() -> (MouseController, Arc<MockBackend>) {
+    let backend = Arc::new(MockBackend::new());
+    (MouseController::with_backend(backend.clone()), backend)
+}
+
+/// 等待条件成立，最多等待 5 秒，超时返回 false
+fn wait_for(mut condition: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !condition() {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    true
+}
+
+/// 像界面一样反复检查热键，返回第一个动作
+fn next_action(hotkeys: &mut HotkeyManager) -> Option<HotkeyAction> {
+    let mut action = None;
+    wait_for(|| {
+        action = hotkeys.check_events(true);
+        action.is_some()
+    });
+    action
+}
+
+#[test]
+fn counts_are_preserved_after_stop() {
+    let (mut controller, backend) = controller();
+
+    controller.start_clicking(ClickSettings::new(MouseButton::Left, 5));
+    assert!(controller.is_running());
+    assert!(wait_for(|| controller.get_click_count() > 10));
+    controller.stop_clicking();
+
+    let clicks = controller.get_click_count();
+    assert!(!controller.is_running());
+    assert!(clicks > 10, "只点击了 {} 次", clicks);
+    assert_eq!(clicks, backend.clicks.load(Ordering::Relaxed));
+
+    // 停止后计数保持不变，直到下次开始
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(controller.get_click_count(), clicks);
+}
+
+#[test]
+fn hold_mode_starts_on_press_and_stops_on_release() {
+    let (mut controller, backend) = controller();
+
+    // 手柄按键不经过系统注册，只靠轮询检测，由测试模拟按下状态
+    let pressed = Arc::new(AtomicBool::new(false));
+    let mut hotkeys = HotkeyManager::new().expect("创建热键管理器失败");
+    let toggle = HotkeyConfig { modifiers: vec![], key: "PadA".to_string() };
+    hotkeys.update_hotkeys(&toggle, &toggle).unwrap();
+    hotkeys.set_pressed_probe({
+        let pressed = Arc::clone(&pressed);
+        move |_| pressed.load(Ordering::Relaxed)
+    });
+
+    let mut first_run = 0;
+    for round in 0..2 {
+        // 与界面中的处理一致：按下开始，松开停止
+        pressed.store(true, Ordering::Relaxed);
+        assert_eq!(next_action(&mut hotkeys), Some(HotkeyAction::HoldStart));
+        controller.start_clicking(ClickSettings::new(MouseButton::Left, 5));
+        assert!(wait_for(|| controller.get_click_count() >= 3));
+
+        pressed.store(false, Ordering::Relaxed);
+        assert_eq!(next_action(&mut hotkeys), Some(HotkeyAction::HoldStop));
+        controller.stop_clicking();
+        assert!(!controller.is_running());
+
+        if round == 0 {
+            first_run = controller.get_click_count();
+        }
+    }
+
+    // 第二次按住重新开始计数
+    let clicks = controller.get_click_count();
+    assert_eq!(clicks + first_run, backend.clicks.load(Ordering::Relaxed));
+    assert!(hotkeys.check_events(true).is_none());
+}
+
+#[test]
+fn stops_by_itself_when_limit_is_reached() {
+    let (mut controller, backend) = controller();
+
+    let mut settings = ClickSettings::new(MouseButton::Left, 2);
+    settings.click_limit = 5;
+    settings.on_limit = LimitAction::Stop;
+    controller.start_clicking(settings);
+    assert!(wait_for(|| !controller.is_running()));
+
+    assert!(controller.limit_reached());
+    assert_eq!(controller.get_click_count(), 5);
+    controller.stop_clicking();
+    assert_eq!(backend.clicks.load(Ordering::Relaxed), 5);
+}
+
+#[test]
+fn config_survives_save_and_load() {
+    let path = std::env::temp_dir().join(format!("rust-ac-test-{}.json", std::process::id()));
+
+    let mut config = AppConfig::new();
+    config.click_interval = 42;
+    config.mouse_button = MouseButton::Right;
+    config.hold_mode = true;
+    config.click_limit = 100;
+    config.on_limit = LimitAction::SwitchProfile("备用".to_string());
+    config.click_points = vec![ClickPoint { x: 10, y: -20 }];
+    config.save_to(&path).unwrap();
+
+    let loaded = AppConfig::load_from(&path);
+    let _ = std::fs::remove_file(&path);
+    let loaded = loaded.unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&loaded).unwrap(),
+        serde_json::to_value(&config).unwrap()
+    );
+    assert_eq!(loaded.click_interval, 42);
+    assert_eq!(loaded.on_limit, LimitAction::SwitchProfile("备用".to_string()));
+}